@@ -0,0 +1,261 @@
+//! Long-lived AI Director sidecar.
+//!
+//! `send_agent_message` used to spawn a fresh `python -m src.llm.interactive` process
+//! for every single message, wait for its one-shot `ready`/`response` handshake, then
+//! kill it — paying the full model/tool-init cost each turn with no memory across turns.
+//! This module keeps one sidecar alive per workspace for as long as its handle lives:
+//! a background task owns the child process and its stdin, `send_message` calls forward
+//! a new `{"text": ...}` line into it over an `mpsc` mailbox and await exactly the
+//! `response` that answers that turn, and incremental `{"type":"token", ...}` lines are
+//! relayed as `agent-token` events as they arrive (alongside the existing `agent-action`
+//! tool events). `reset_agent_session` sends `{"type": "reset"}` to clear server-side
+//! history without tearing down the process.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::CommandEvent;
+use tauri_plugin_shell::ShellExt;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::MikupError;
+
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Emitted once per tool call the AI Director makes during a turn.
+#[derive(Clone, serde::Serialize)]
+struct AgentActionPayload {
+    tool: String,
+    time_secs: Option<f64>,
+}
+
+/// Emitted for each incremental token as the AI Director streams its reply.
+#[derive(Clone, serde::Serialize)]
+struct AgentTokenPayload {
+    text: String,
+}
+
+/// The turn currently awaiting a `response` line, so we can return something sensible
+/// even if the sidecar's `response` arrives without a `text` field (fall back to the
+/// tokens we've already streamed out).
+struct PendingTurn {
+    streamed_text: String,
+    reply: oneshot::Sender<Result<String, MikupError>>,
+}
+
+enum Mailbox {
+    SendMessage {
+        text: String,
+        reply: oneshot::Sender<Result<String, MikupError>>,
+    },
+    Reset {
+        reply: oneshot::Sender<Result<(), MikupError>>,
+    },
+}
+
+/// A live handle to a spawned sidecar. Cloning shares the same mailbox, so multiple
+/// commands can talk to the one running process without holding the task itself.
+#[derive(Clone)]
+pub struct AgentSidecarHandle {
+    mailbox: mpsc::UnboundedSender<Mailbox>,
+}
+
+impl AgentSidecarHandle {
+    pub async fn send_message(&self, text: String) -> Result<String, MikupError> {
+        let (reply, rx) = oneshot::channel();
+        self.mailbox
+            .send(Mailbox::SendMessage { text, reply })
+            .map_err(|_| MikupError::Internal("AI Director sidecar is not running".to_string()))?;
+
+        match tokio::time::timeout(RESPONSE_TIMEOUT, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(MikupError::Internal(
+                "AI Director sidecar dropped the request".to_string(),
+            )),
+            Err(_) => Err(MikupError::Timeout("AI Director timed out".to_string())),
+        }
+    }
+
+    pub async fn reset(&self) -> Result<(), MikupError> {
+        let (reply, rx) = oneshot::channel();
+        self.mailbox
+            .send(Mailbox::Reset { reply })
+            .map_err(|_| MikupError::Internal("AI Director sidecar is not running".to_string()))?;
+        rx.await.map_err(|_| {
+            MikupError::Internal("AI Director sidecar dropped the request".to_string())
+        })?
+    }
+}
+
+/// Spawns `python -m src.llm.interactive` once and keeps it alive for the lifetime of
+/// the returned handle, forwarding `send_message`/`reset` calls into its stdin and
+/// relaying its stdout (`agent-token`, `agent-action`, and the final response) back out.
+///
+/// Protocol (newline-delimited JSON), extended with a `reset` message over the
+/// original one-shot handshake:
+///   Rust  → Python stdin:  `{"text": "<user message>"}\n` | `{"type": "reset"}\n`
+///   Python → Rust stdout:  `{"type": "ready"}\n`              (once, on startup)
+///                          `{"tool": "<name>", ...}\n`         (zero or more tool calls)
+///                          `{"type": "token", "text": "..."}\n` (zero or more, streamed)
+///                          `{"type": "response", "text": "..."}\n`
+pub async fn spawn(
+    app: AppHandle,
+    python_path: String,
+    project_root: PathBuf,
+    workspace_dir: String,
+) -> Result<AgentSidecarHandle, MikupError> {
+    let (mut rx, mut child) = app
+        .shell()
+        .command(&python_path)
+        .current_dir(&project_root)
+        .args(["-m", "src.llm.interactive"])
+        .env("WORKSPACE_DIR", &workspace_dir)
+        .spawn()
+        .map_err(|e| MikupError::Internal(format!("Failed to spawn AI Director: {e}")))?;
+
+    let (tx, mut mailbox) = mpsc::unbounded_channel::<Mailbox>();
+    let handle = AgentSidecarHandle { mailbox: tx };
+
+    tokio::spawn(async move {
+        fn send_line(
+            child: &mut tauri_plugin_shell::process::CommandChild,
+            text: &str,
+        ) -> Result<(), MikupError> {
+            let line = serde_json::json!({"text": text}).to_string() + "\n";
+            child
+                .write(line.as_bytes())
+                .map_err(|e| MikupError::Internal(format!("Failed to send message to AI Director: {e}")))
+        }
+
+        let mut stdout_buf = String::new();
+        let mut ready = false;
+        let mut pending: Option<PendingTurn> = None;
+        // A message sent before the sidecar's initial `ready` line arrives is held here
+        // and flushed once it does, mirroring the original spawn-per-request handshake.
+        let mut pending_send: Option<(String, oneshot::Sender<Result<String, MikupError>>)> = None;
+
+        loop {
+            tokio::select! {
+                maybe_cmd = mailbox.recv() => {
+                    match maybe_cmd {
+                        Some(Mailbox::SendMessage { text, reply }) => {
+                            if pending.is_some() || pending_send.is_some() {
+                                let _ = reply.send(Err(MikupError::Internal(
+                                    "AI Director is still answering the previous message".to_string(),
+                                )));
+                                continue;
+                            }
+                            if !ready {
+                                pending_send = Some((text, reply));
+                                continue;
+                            }
+                            match send_line(&mut child, &text) {
+                                Ok(()) => pending = Some(PendingTurn { streamed_text: String::new(), reply }),
+                                Err(e) => { let _ = reply.send(Err(e)); }
+                            }
+                        }
+                        Some(Mailbox::Reset { reply }) => {
+                            if pending.is_some() || pending_send.is_some() {
+                                let _ = reply.send(Err(MikupError::Internal(
+                                    "AI Director is still answering the previous message".to_string(),
+                                )));
+                                continue;
+                            }
+                            let line = serde_json::json!({"type": "reset"}).to_string() + "\n";
+                            let result = child.write(line.as_bytes()).map_err(|e| {
+                                MikupError::Internal(format!("Failed to reset AI Director: {e}"))
+                            });
+                            let _ = reply.send(result);
+                        }
+                        None => break, // every handle dropped — nothing left to serve
+                    }
+                }
+                maybe_event = rx.recv() => {
+                    match maybe_event {
+                        Some(CommandEvent::Stdout(chunk)) => {
+                            stdout_buf.push_str(&String::from_utf8_lossy(&chunk));
+                            while let Some(pos) = stdout_buf.find('\n') {
+                                let line: String = stdout_buf.drain(..=pos).collect();
+                                let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
+                                if trimmed.is_empty() {
+                                    continue;
+                                }
+                                let Ok(json_val) = serde_json::from_str::<serde_json::Value>(trimmed) else {
+                                    continue;
+                                };
+                                let msg_type = json_val.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                                match msg_type {
+                                    "ready" => {
+                                        ready = true;
+                                        if let Some((text, reply)) = pending_send.take() {
+                                            match send_line(&mut child, &text) {
+                                                Ok(()) => pending = Some(PendingTurn { streamed_text: String::new(), reply }),
+                                                Err(e) => { let _ = reply.send(Err(e)); }
+                                            }
+                                        }
+                                    }
+                                    "token" => {
+                                        let token_text = json_val
+                                            .get("text")
+                                            .and_then(|t| t.as_str())
+                                            .unwrap_or("")
+                                            .to_string();
+                                        if let Some(turn) = pending.as_mut() {
+                                            turn.streamed_text.push_str(&token_text);
+                                        }
+                                        let _ = app.emit("agent-token", AgentTokenPayload { text: token_text });
+                                    }
+                                    "response" => {
+                                        let response_text = json_val
+                                            .get("text")
+                                            .and_then(|t| t.as_str())
+                                            .map(str::to_string);
+                                        if let Some(turn) = pending.take() {
+                                            let final_text = response_text.unwrap_or(turn.streamed_text);
+                                            let _ = turn.reply.send(Ok(final_text));
+                                        }
+                                    }
+                                    _ => {
+                                        if let Some(tool_name) = json_val.get("tool").and_then(|t| t.as_str()) {
+                                            let time_secs = json_val.get("time_secs").and_then(|t| t.as_f64());
+                                            let _ = app.emit(
+                                                "agent-action",
+                                                AgentActionPayload {
+                                                    tool: tool_name.to_string(),
+                                                    time_secs,
+                                                },
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Some(CommandEvent::Stderr(_)) => {
+                            // Python logging to stderr — ignored by design.
+                        }
+                        Some(CommandEvent::Terminated(status)) => {
+                            let fault = || MikupError::PipelineFailed {
+                                exit_code: status.code,
+                                message: "AI Director exited unexpectedly".to_string(),
+                            };
+                            if let Some(turn) = pending.take() {
+                                let _ = turn.reply.send(Err(fault()));
+                            }
+                            if let Some((_, reply)) = pending_send.take() {
+                                let _ = reply.send(Err(fault()));
+                            }
+                            break;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        let _ = child.kill();
+    });
+
+    Ok(handle)
+}
@@ -0,0 +1,446 @@
+//! Long-lived playback actor for the live DSP metering stream.
+//!
+//! `stream_audio_metrics` used to build a decoder + player + analyzers inline and run
+//! them to completion with no way to pause, seek, or re-balance stems mid-stream. This
+//! module lifts that loop into a task that owns those resources for the lifetime of the
+//! stream and takes transport commands over an `mpsc` channel, so the frontend can drive
+//! playback (`audio_transport`) independently of the frame-by-frame render loop.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use crate::dsp::clocked_queue::ClockedQueue;
+use crate::dsp::loudness::LoudnessAnalyzer;
+use crate::dsp::player::{interleave_mono, AudioOutputPlayer, MonoResampler};
+use crate::dsp::spatial::SpatialAnalyzer;
+use crate::dsp::spectral::SpectralAnalyzer;
+use crate::dsp::{FrameClock, MikupAudioDecoder, SharedStemStates};
+use crate::error::MikupError;
+use crate::metrics_broadcast::MetricsBroadcastHandle;
+use crate::transcription::TranscriptionHandle;
+use crate::{DspCompletePayload, DspFramePayload, ProgressPayload, LISSAJOUS_MAX_POINTS, MIN_EMIT_INTERVAL_MS};
+
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Capacity of the handoff queue between the decode/mix step and the metering/output step
+/// below — just enough slack to let a seek drop stale in-flight frames via `pop_next`
+/// without letting decode run meaningfully ahead of what's actually been output.
+const PLAYBACK_QUEUE_CAPACITY: usize = 4;
+
+/// Transport command sent to a running audio actor via [`AudioActorHandle::send`].
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(tag = "type", content = "content")]
+pub enum AudioCommand {
+    Play,
+    Pause,
+    Seek { secs: f64 },
+    SetMasterVolume { volume: f32 },
+    SetStemGain { stem: String, gain: f32 },
+    SetStemMute { stem: String, muted: bool },
+    Stop,
+}
+
+/// Combined transport state broadcast back to the frontend via the `dsp-transport`
+/// event after every command that changes it, so the UI can render a proper transport
+/// bar (play/pause, scrub position, master volume) instead of stop-only.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TransportStatus {
+    playing: bool,
+    position_secs: f64,
+    master_volume: f32,
+    ended: bool,
+}
+
+/// A live handle to a spawned audio actor. Cloning shares the same underlying channel,
+/// so `audio_transport` can forward commands without holding the render loop itself.
+#[derive(Clone)]
+pub struct AudioActorHandle {
+    commands: mpsc::UnboundedSender<AudioCommand>,
+}
+
+impl AudioActorHandle {
+    pub fn send(&self, command: AudioCommand) -> Result<(), MikupError> {
+        self.commands
+            .send(command)
+            .map_err(|_| MikupError::NotFound("No active audio stream to control".to_string()))
+    }
+}
+
+/// Spawns the blocking decode/analyze/emit loop on a dedicated thread and returns a
+/// handle for sending transport commands plus the `JoinHandle` the caller should await
+/// to know when the stream ends (naturally, via `Stop`, or superseded by a new stream).
+pub fn spawn(
+    app: AppHandle,
+    mut decoder: MikupAudioDecoder,
+    stem_states: SharedStemStates,
+    generation: Arc<AtomicU64>,
+    my_gen: u64,
+    start_time_secs: f64,
+    metrics_broadcast: Option<MetricsBroadcastHandle>,
+    transcription: Option<TranscriptionHandle>,
+) -> (AudioActorHandle, tokio::task::JoinHandle<Result<(), MikupError>>) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<AudioCommand>();
+    let handle = AudioActorHandle { commands: tx };
+
+    let join_handle = tokio::task::spawn_blocking(move || {
+        let sample_rate = decoder.target_sample_rate();
+        let frame_size = decoder.frame_size();
+
+        let mut loudness =
+            LoudnessAnalyzer::new(sample_rate).map_err(|e| MikupError::Internal(e.to_string()))?;
+        let spatial = SpatialAnalyzer::new();
+        let mut spectral = SpectralAnalyzer::new(sample_rate, frame_size);
+
+        // Audio output: create a cpal player and a resampler (48kHz → hardware rate).
+        // Failure to open the output device is non-fatal — analysis continues without audio.
+        let audio_player = AudioOutputPlayer::new_default(0.2)
+            .map_err(|e| eprintln!("[mikup] Audio output unavailable: {e}"))
+            .ok();
+        let mut audio_resampler = audio_player.as_ref().and_then(|p| {
+            MonoResampler::new(sample_rate, p.hardware_sample_rate())
+                .map_err(|e| eprintln!("[mikup] Audio resampler init failed: {e}"))
+                .ok()
+        });
+        if let Some(ref p) = audio_player {
+            if let Err(e) = p.start() {
+                eprintln!("[mikup] Audio player start failed: {e}");
+            }
+        }
+
+        let mut frame_queue = ClockedQueue::new(PLAYBACK_QUEUE_CAPACITY);
+        let mut frame_index: u64 = 0;
+        let min_interval = Duration::from_millis(MIN_EMIT_INTERVAL_MS);
+        let mut last_emit: Option<std::time::Instant> = None;
+        let mut eof_natural = false;
+        let mut decoder_eof = false;
+        let mut playing = true;
+        let mut master_volume: f32 = 1.0;
+        // Absolute playback position of the start of the current segment; advances by
+        // `frame_index * frame_size / sample_rate` and is rebased whenever we seek.
+        let mut segment_origin_secs = start_time_secs;
+        // Wall-clock reference for pacing `frame_queue` pops against real elapsed time:
+        // pairs the `Instant` playback last (re)started at with the `FrameClock` that
+        // plays at that instant. Cleared on `Seek`, `Pause`, and `Play` so paused time and
+        // a fresh seek position never skew how "late" a frame looks.
+        let mut playback_epoch: Option<(std::time::Instant, FrameClock)> = None;
+
+        let position_secs = |frame_index: u64, segment_origin_secs: f64| {
+            segment_origin_secs + (frame_index as f64 * frame_size as f64 / sample_rate as f64)
+        };
+        let emit_transport = |app: &AppHandle, playing: bool, position_secs: f64, master_volume: f32, ended: bool| {
+            let _ = app.emit(
+                "dsp-transport",
+                TransportStatus {
+                    playing,
+                    position_secs,
+                    master_volume,
+                    ended,
+                },
+            );
+        };
+
+        'stream: loop {
+            if generation.load(Ordering::Relaxed) != my_gen {
+                break;
+            }
+
+            while let Ok(command) = rx.try_recv() {
+                match command {
+                    AudioCommand::Play => {
+                        playing = true;
+                        // Resuming shouldn't count time spent paused against the next
+                        // frame's schedule — re-establish the epoch against whichever
+                        // frame is popped next.
+                        playback_epoch = None;
+                        emit_transport(
+                            &app,
+                            playing,
+                            position_secs(frame_index, segment_origin_secs),
+                            master_volume,
+                            false,
+                        );
+                    }
+                    AudioCommand::Pause => {
+                        playing = false;
+                        playback_epoch = None;
+                        emit_transport(
+                            &app,
+                            playing,
+                            position_secs(frame_index, segment_origin_secs),
+                            master_volume,
+                            false,
+                        );
+                    }
+                    AudioCommand::Seek { secs } => {
+                        let target = secs.max(0.0);
+                        if let Err(e) = decoder.seek(target as f32) {
+                            let _ = app.emit("dsp-error", MikupError::Internal(e.to_string()));
+                        } else {
+                            segment_origin_secs = target;
+                            frame_index = 0;
+                            decoder_eof = false;
+                            playback_epoch = None;
+                            // Anything still sitting in the handoff queue was decoded
+                            // against the pre-seek position, so it's stale — drop it
+                            // rather than output/meter frames for the wrong position.
+                            while frame_queue.pop_next().is_some() {}
+                            emit_transport(&app, playing, target, master_volume, false);
+                        }
+                    }
+                    AudioCommand::SetMasterVolume { volume } => {
+                        master_volume = volume.clamp(0.0, 4.0);
+                        emit_transport(
+                            &app,
+                            playing,
+                            position_secs(frame_index, segment_origin_secs),
+                            master_volume,
+                            false,
+                        );
+                    }
+                    AudioCommand::SetStemGain { stem, gain } => {
+                        if let Ok(mut map) = stem_states.write() {
+                            if let Some(state) = map.get_mut(&stem) {
+                                state.gain = gain.clamp(0.0, 4.0);
+                            }
+                        }
+                    }
+                    AudioCommand::SetStemMute { stem, muted } => {
+                        if let Ok(mut map) = stem_states.write() {
+                            if let Some(state) = map.get_mut(&stem) {
+                                state.is_muted = muted;
+                            }
+                        }
+                    }
+                    AudioCommand::Stop => {
+                        emit_transport(
+                            &app,
+                            false,
+                            position_secs(frame_index, segment_origin_secs),
+                            master_volume,
+                            true,
+                        );
+                        break 'stream;
+                    }
+                }
+            }
+
+            if !playing {
+                std::thread::sleep(PAUSED_POLL_INTERVAL);
+                continue;
+            }
+
+            // Keep decode running ahead of playback, bounded by the queue's capacity, so a
+            // frame is ready the instant its clock comes due without racing arbitrarily far
+            // ahead of what's actually being presented.
+            if !decoder_eof && frame_queue.len() < PLAYBACK_QUEUE_CAPACITY {
+                match decoder.read_frame() {
+                    Ok(Some(f)) => {
+                        frame_queue.push(f.clock, f);
+                    }
+                    Ok(None) => decoder_eof = true,
+                    Err(e) => {
+                        let error = MikupError::Internal(e.to_string());
+                        let _ = app.emit("dsp-error", error.clone());
+                        return Err(error);
+                    }
+                }
+            }
+
+            // Once decode has hit EOF and the queue has drained, there's nothing left to
+            // present.
+            let Some((clock, frame)) = frame_queue.pop_next() else {
+                if decoder_eof {
+                    eof_natural = true;
+                    break;
+                }
+                continue;
+            };
+
+            let (epoch_instant, epoch_clock) =
+                *playback_epoch.get_or_insert((std::time::Instant::now(), clock));
+            let due_at = epoch_instant
+                + Duration::from_secs_f32(
+                    (clock.as_seconds(sample_rate) - epoch_clock.as_seconds(sample_rate)).max(0.0),
+                );
+            let now = std::time::Instant::now();
+            if now < due_at {
+                // Decode is running ahead of wall-clock playback: hand the frame back
+                // instead of presenting it early, and loop around so Pause/Seek/Stop stay
+                // responsive while we wait.
+                frame_queue.unpop(clock, frame);
+                std::thread::sleep((due_at - now).min(PAUSED_POLL_INTERVAL));
+                continue;
+            }
+
+            // Only treat this as falling behind if the *next* queued frame is already due
+            // too — otherwise the queue simply holds decode-ahead frames for later, which
+            // isn't a backlog.
+            let next_also_due = frame_queue.peek_clock().is_some_and(|next_clock| {
+                let next_due_at = epoch_instant
+                    + Duration::from_secs_f32(
+                        (next_clock.as_seconds(sample_rate) - epoch_clock.as_seconds(sample_rate))
+                            .max(0.0),
+                    );
+                now >= next_due_at
+            });
+            let frame = if next_also_due {
+                // Processing has fallen behind real time and a backlog of overdue frames
+                // has built up — jump straight to the newest rather than grinding through
+                // stale ones one at a time.
+                frame_queue.unpop(clock, frame);
+                let (_, latest) = frame_queue
+                    .pop_latest()
+                    .expect("queue is non-empty after unpop");
+                latest
+            } else {
+                frame
+            };
+
+            let timestamp_secs = frame_index as f32 * frame_size as f32 / sample_rate as f32;
+
+            let loudness_metrics = match loudness.process_frame(&frame) {
+                Ok(m) => m,
+                Err(e) => {
+                    let error = MikupError::Internal(e.to_string());
+                    let _ = app.emit("dsp-error", error.clone());
+                    return Err(error);
+                }
+            };
+
+            let spatial_metrics = spatial.process_frame(&frame);
+            let spectral_metrics = spectral.process_frame(&frame);
+
+            // Push mixed audio (dialogue + background) to cpal output player.
+            if let (Some(ref player), Some(ref mut resampler)) =
+                (&audio_player, &mut audio_resampler)
+            {
+                let mixed: Vec<f32> = frame
+                    .dialogue_raw
+                    .iter()
+                    .zip(frame.background_raw.iter())
+                    .map(|(d, b)| ((d + b) * master_volume).clamp(-1.0, 1.0))
+                    .collect();
+                let resampled = resampler.process(&mixed);
+                let interleaved = interleave_mono(&resampled, player.channels());
+                player.push_interleaved_nonblocking(&interleaved);
+            }
+
+            frame_index += 1;
+
+            // Throttle: skip emit if the minimum interval hasn't elapsed yet.
+            let now = std::time::Instant::now();
+            let should_emit = match last_emit {
+                None => true,
+                Some(t) => now.duration_since(t) >= min_interval,
+            };
+            if !should_emit {
+                continue;
+            }
+            last_emit = Some(now);
+
+            // Subsample Lissajous points so each frame emits at most LISSAJOUS_MAX_POINTS.
+            let step = (spatial_metrics.lissajous_points.len() / LISSAJOUS_MAX_POINTS).max(1);
+            let lissajous_points: Vec<[f32; 2]> = spatial_metrics
+                .lissajous_points
+                .iter()
+                .step_by(step)
+                .map(|p| [p.x, p.y])
+                .collect();
+
+            let payload = DspFramePayload {
+                frame_index,
+                timestamp_secs,
+                dialogue_momentary_lufs: loudness_metrics.dialogue.momentary_lufs,
+                dialogue_short_term_lufs: loudness_metrics.dialogue.short_term_lufs,
+                dialogue_true_peak_dbtp: loudness_metrics.dialogue.true_peak_dbtp,
+                dialogue_crest_factor: loudness_metrics.dialogue.crest_factor,
+                background_momentary_lufs: loudness_metrics.background.momentary_lufs,
+                background_short_term_lufs: loudness_metrics.background.short_term_lufs,
+                background_true_peak_dbtp: loudness_metrics.background.true_peak_dbtp,
+                background_crest_factor: loudness_metrics.background.crest_factor,
+                phase_correlation: spatial_metrics.phase_correlation,
+                lissajous_points,
+                dialogue_centroid_hz: spectral_metrics.dialogue_centroid_hz,
+                background_centroid_hz: spectral_metrics.background_centroid_hz,
+                speech_pocket_masked: spectral_metrics.speech_pocket_masked,
+                masked_bark_bands: spectral_metrics.masked_bark_bands.clone(),
+                speech_pocket_masked_fraction: spectral_metrics.speech_pocket_masked_fraction,
+                dialogue_speech_energy: spectral_metrics.dialogue_speech_energy,
+                background_speech_energy: spectral_metrics.background_speech_energy,
+                snr_db: spectral_metrics.snr_db,
+                dialogue_flux: spectral_metrics.dialogue_flux,
+                background_flux: spectral_metrics.background_flux,
+                dialogue_rolloff_hz: spectral_metrics.dialogue_rolloff_hz,
+                background_rolloff_hz: spectral_metrics.background_rolloff_hz,
+                dialogue_flatness: spectral_metrics.dialogue_flatness,
+                background_flatness: spectral_metrics.background_flatness,
+            };
+
+            if let Some(ref broadcast) = metrics_broadcast {
+                broadcast.send_frame(payload.clone());
+            }
+            if let Some(ref transcription) = transcription {
+                transcription.push_frame(
+                    frame.dialogue_raw.clone(),
+                    timestamp_secs,
+                    spectral_metrics.speech_pocket_masked,
+                    spectral_metrics.snr_db,
+                );
+            }
+            #[cfg(feature = "metrics")]
+            crate::telemetry::record_dsp_frame();
+            let _ = app.emit("dsp-frame", payload);
+        }
+
+        if let Some(ref player) = audio_player {
+            player.mark_producer_finished();
+        }
+
+        // Warn if any stems were shorter than others and were padded with silence.
+        if decoder.alignment_mismatch_detected {
+            let _ = app.emit(
+                "process-status",
+                ProgressPayload {
+                    stage: "DSP_WARNING".to_string(),
+                    progress: 0,
+                    message: "Stem length mismatch: one or more stems are shorter than others and were padded with silence. Spatial and ducking analysis may be affected near the tail.".to_string(),
+                },
+            );
+        }
+
+        // Only emit the completion event when we reached EOF naturally (not cancelled/stopped).
+        if eof_natural {
+            let final_metrics = loudness.final_metrics();
+            let complete_payload = DspCompletePayload {
+                total_frames: frame_index,
+                dialogue_integrated_lufs: final_metrics.dialogue.integrated_lufs,
+                dialogue_loudness_range_lu: final_metrics.dialogue.loudness_range_lu,
+                background_integrated_lufs: final_metrics.background.integrated_lufs,
+                background_loudness_range_lu: final_metrics.background.loudness_range_lu,
+            };
+            if let Some(ref broadcast) = metrics_broadcast {
+                broadcast.send_complete(complete_payload.clone());
+            }
+            #[cfg(feature = "metrics")]
+            crate::telemetry::record_final_lufs(
+                complete_payload.dialogue_integrated_lufs,
+                complete_payload.background_integrated_lufs,
+            );
+            let _ = app.emit("dsp-complete", complete_payload);
+            emit_transport(
+                &app,
+                false,
+                position_secs(frame_index, segment_origin_secs),
+                master_volume,
+                true,
+            );
+        }
+
+        Ok::<(), MikupError>(())
+    });
+
+    (handle, join_handle)
+}
@@ -0,0 +1,115 @@
+//! A small bounded, clock-ordered handoff queue between the mixer thread (which produces
+//! [`SyncedAudioFrame`](crate::dsp::SyncedAudioFrame)s at whatever rate decoding allows) and
+//! an output/consumer stage that wants to present them at a steady, clock-driven pace. Plain
+//! FIFO isn't quite enough for that consumer: it also needs to catch up by dropping stale
+//! frames when it falls behind, and to hand an early frame back when it wakes up ahead of
+//! schedule rather than losing it.
+
+use std::collections::VecDeque;
+
+use crate::dsp::FrameClock;
+
+/// Bounded FIFO of `(FrameClock, T)` pairs. "Bounded" means `push` never grows the queue past
+/// `capacity` — it drops the oldest entry to make room instead, since an unbounded backlog of
+/// stale frames is worse for a live consumer than losing the oldest one.
+#[derive(Debug, Clone)]
+pub struct ClockedQueue<T> {
+    capacity: usize,
+    items: VecDeque<(FrameClock, T)>,
+}
+
+impl<T> ClockedQueue<T> {
+    /// `capacity` must be at least 1; a queue that can't hold anything isn't useful as a
+    /// handoff point.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            items: VecDeque::with_capacity(capacity.max(1)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Appends `item` at `clock`, dropping and returning the oldest entry first if the queue
+    /// was already at capacity.
+    pub fn push(&mut self, clock: FrameClock, item: T) -> Option<(FrameClock, T)> {
+        let dropped = if self.items.len() >= self.capacity {
+            self.items.pop_front()
+        } else {
+            None
+        };
+        self.items.push_back((clock, item));
+        dropped
+    }
+
+    /// Pushes `item` back onto the *front* of the queue, for a consumer that pulled a frame
+    /// and decided it's too early to present yet. Does not count against `capacity` eviction
+    /// since it's returning something the queue already held, not adding new data.
+    pub fn unpop(&mut self, clock: FrameClock, item: T) {
+        self.items.push_front((clock, item));
+    }
+
+    /// Removes and returns the oldest (next-to-present) entry.
+    pub fn pop_next(&mut self) -> Option<(FrameClock, T)> {
+        self.items.pop_front()
+    }
+
+    /// Drops every entry except the newest, returning it — catch-up behavior for a consumer
+    /// that has fallen behind and only cares about the most current frame. Returns `None`
+    /// (and leaves the queue empty either way) if the queue was already empty.
+    pub fn pop_latest(&mut self) -> Option<(FrameClock, T)> {
+        let latest = self.items.pop_back();
+        self.items.clear();
+        latest
+    }
+
+    /// The clock of the next entry [`Self::pop_next`] would return, without removing it.
+    pub fn peek_clock(&self) -> Option<FrameClock> {
+        self.items.front().map(|(clock, _)| *clock)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_beyond_capacity_drops_the_oldest_entry() {
+        let mut queue = ClockedQueue::new(2);
+        assert!(queue.push(FrameClock(0), "a").is_none());
+        assert!(queue.push(FrameClock(1), "b").is_none());
+        let dropped = queue.push(FrameClock(2), "c");
+        assert_eq!(dropped, Some((FrameClock(0), "a")));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.peek_clock(), Some(FrameClock(1)));
+    }
+
+    #[test]
+    fn pop_latest_drains_everything_but_the_newest() {
+        let mut queue = ClockedQueue::new(4);
+        queue.push(FrameClock(0), "a");
+        queue.push(FrameClock(1), "b");
+        queue.push(FrameClock(2), "c");
+
+        assert_eq!(queue.pop_latest(), Some((FrameClock(2), "c")));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn unpop_puts_a_frame_back_at_the_front() {
+        let mut queue = ClockedQueue::new(4);
+        queue.push(FrameClock(5), "a");
+        let (clock, item) = queue.pop_next().unwrap();
+        assert_eq!(clock, FrameClock(5));
+
+        queue.unpop(clock, item);
+        assert_eq!(queue.peek_clock(), Some(FrameClock(5)));
+        assert_eq!(queue.pop_next(), Some((FrameClock(5), "a")));
+    }
+}
@@ -0,0 +1,309 @@
+//! Real-time loudness/spectral monitoring over a live capture stream.
+//!
+//! `SpectralAnalyzer` and `OfflineLoudnessScanner` are both pull-based over already-decoded
+//! audio — fine for finished stem files, useless for watching a live mix session. This
+//! module splits the same problem into a pure, hop-at-a-time core ([`LiveLoudnessSpectralMonitor`])
+//! that reuses `SpectralAnalyzer::process_frame` and an `EbuR128` momentary/short-term meter
+//! directly (so live and offline numbers agree on identical samples), and a `cpal`-backed
+//! driver ([`LiveCaptureStream`]) that pulls from the default input device and feeds it hops.
+//! Events stream out through a plain callback, mirroring `OfflineLoudnessScanner::scan`'s
+//! `ScanEvent`/`on_event` shape rather than introducing a new reporting convention.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample};
+use crossbeam_queue::ArrayQueue;
+use ebur128::{EbuR128, Mode};
+
+use crate::dsp::spectral::{SpectralAnalyzer, SpectralMetrics};
+use crate::dsp::SyncedAudioFrame;
+
+const LUFS_FLOOR: f32 = -70.0;
+const LUFS_CEILING: f32 = 0.0;
+/// How many hops of headroom the capture queue gets before the input callback starts
+/// dropping samples. A live monitor favors staying current over never losing a sample.
+const QUEUE_HOPS_CAPACITY: usize = 16;
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Debug)]
+pub enum LiveMonitorError {
+    InvalidHopSize,
+    NoInputDevice,
+    UnsupportedInputConfig(String),
+    Meter(String),
+    Stream(String),
+}
+
+impl std::fmt::Display for LiveMonitorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHopSize => write!(f, "hop_size must be > 0"),
+            Self::NoInputDevice => write!(f, "No default input audio device found"),
+            Self::UnsupportedInputConfig(msg) => {
+                write!(f, "Unsupported input device config: {msg}")
+            }
+            Self::Meter(msg) => write!(f, "EBU R128 meter failure: {msg}"),
+            Self::Stream(msg) => write!(f, "Input stream failure: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LiveMonitorError {}
+
+/// One hop's worth of live metrics, mirroring `ScanEvent`'s shape so callers that already
+/// handle `OfflineLoudnessScanner::scan` events can follow the same pattern here.
+#[derive(Debug, Clone)]
+pub enum LiveMonitorEvent {
+    Started { sample_rate: u32 },
+    Metrics {
+        momentary_lufs: f32,
+        short_term_lufs: f32,
+        spectral: SpectralMetrics,
+    },
+    Stopped,
+}
+
+/// Pure, pull-based monitoring core: feed it one hop of mono samples at a time and it
+/// returns the same momentary/short-term LUFS and spectral descriptors the offline scanner
+/// would compute over the identical samples. Has no knowledge of where the samples came
+/// from, so it works equally well driven by `cpal` or by a test harness pushing synthetic
+/// buffers.
+pub struct LiveLoudnessSpectralMonitor {
+    sample_rate: u32,
+    hop_size: usize,
+    meter: EbuR128,
+    spectral: SpectralAnalyzer,
+}
+
+impl LiveLoudnessSpectralMonitor {
+    pub fn new(sample_rate: u32, hop_size: usize) -> Result<Self, LiveMonitorError> {
+        if hop_size == 0 {
+            return Err(LiveMonitorError::InvalidHopSize);
+        }
+        let meter = EbuR128::new(1, sample_rate, Mode::M | Mode::S)
+            .map_err(|e| LiveMonitorError::Meter(e.to_string()))?;
+
+        Ok(Self {
+            sample_rate,
+            hop_size,
+            meter,
+            spectral: SpectralAnalyzer::new(sample_rate, hop_size),
+        })
+    }
+
+    pub fn hop_size(&self) -> usize {
+        self.hop_size
+    }
+
+    /// Processes exactly one hop of mono samples (`len()` should equal `hop_size`, though a
+    /// short final hop is accepted) and returns the resulting metrics event. A live capture
+    /// device has no separate dialogue/background split, so the hop is analyzed on
+    /// `SpectralAnalyzer`'s dialogue channel with the background channel held silent — the
+    /// background-side fields of `spectral` are therefore always zero and should be ignored.
+    pub fn process_hop(&mut self, samples: &[f32]) -> Result<LiveMonitorEvent, LiveMonitorError> {
+        self.meter
+            .add_frames_f32(samples)
+            .map_err(|e| LiveMonitorError::Meter(e.to_string()))?;
+
+        let frame = SyncedAudioFrame {
+            sample_rate: self.sample_rate,
+            dialogue_raw: samples.to_vec(),
+            background_raw: vec![0.0; samples.len()],
+            ..SyncedAudioFrame::default()
+        };
+        let spectral = self.spectral.process_frame(&frame);
+
+        Ok(LiveMonitorEvent::Metrics {
+            momentary_lufs: read_lufs(self.meter.loudness_momentary()),
+            short_term_lufs: read_lufs(self.meter.loudness_shortterm()),
+            spectral,
+        })
+    }
+}
+
+/// Owns a running `cpal` input stream and the background thread that drains it in
+/// `hop_size` chunks through a [`LiveLoudnessSpectralMonitor`]. Dropping this stops the
+/// stream and joins the processing thread.
+pub struct LiveCaptureStream {
+    _stream: cpal::Stream,
+    stop: Arc<AtomicBool>,
+    processing_thread: Option<thread::JoinHandle<()>>,
+}
+
+impl LiveCaptureStream {
+    /// Opens the default input device and starts streaming `LiveMonitorEvent`s through
+    /// `on_event` until dropped. Input channels beyond the first are downmixed to mono by
+    /// averaging, matching `OfflineLoudnessScanner`'s downmix for gated-loudness metering.
+    pub fn start_default_input<F>(hop_size: usize, on_event: F) -> Result<Self, LiveMonitorError>
+    where
+        F: FnMut(LiveMonitorEvent) + Send + 'static,
+    {
+        if hop_size == 0 {
+            return Err(LiveMonitorError::InvalidHopSize);
+        }
+
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or(LiveMonitorError::NoInputDevice)?;
+        let supported = device
+            .default_input_config()
+            .map_err(|e| LiveMonitorError::UnsupportedInputConfig(e.to_string()))?;
+        let sample_format = supported.sample_format();
+        let sample_rate = supported.sample_rate().0;
+        let config = supported.config();
+
+        // The input callback downmixes before pushing, so the queue holds mono samples.
+        let queue = Arc::new(ArrayQueue::<f32>::new(hop_size * QUEUE_HOPS_CAPACITY));
+
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => {
+                build_input_stream::<f32>(&device, &config, Arc::clone(&queue))?
+            }
+            cpal::SampleFormat::I16 => {
+                build_input_stream::<i16>(&device, &config, Arc::clone(&queue))?
+            }
+            cpal::SampleFormat::U16 => {
+                build_input_stream::<u16>(&device, &config, Arc::clone(&queue))?
+            }
+            other => {
+                return Err(LiveMonitorError::UnsupportedInputConfig(format!(
+                    "unsupported sample format: {other:?}"
+                )))
+            }
+        };
+        stream
+            .play()
+            .map_err(|e| LiveMonitorError::Stream(e.to_string()))?;
+
+        let mut monitor = LiveLoudnessSpectralMonitor::new(sample_rate, hop_size)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = Arc::clone(&stop);
+        let on_event = Arc::new(Mutex::new(on_event));
+        let emit = Arc::clone(&on_event);
+        emit.lock()
+            .unwrap_or_else(|e| e.into_inner())(LiveMonitorEvent::Started { sample_rate });
+
+        let processing_thread = thread::spawn(move || {
+            let mut mono_hop = Vec::with_capacity(hop_size);
+            while !stop_for_thread.load(Ordering::Relaxed) {
+                match queue.pop() {
+                    Some(sample) => {
+                        mono_hop.push(sample);
+                        if mono_hop.len() >= hop_size {
+                            if let Ok(event) = monitor.process_hop(&mono_hop) {
+                                on_event.lock().unwrap_or_else(|e| e.into_inner())(event);
+                            }
+                            mono_hop.clear();
+                        }
+                    }
+                    None => thread::sleep(QUEUE_POLL_INTERVAL),
+                }
+            }
+            on_event.lock().unwrap_or_else(|e| e.into_inner())(LiveMonitorEvent::Stopped);
+        });
+
+        Ok(Self {
+            _stream: stream,
+            stop,
+            processing_thread: Some(processing_thread),
+        })
+    }
+
+    /// Stops the capture stream and blocks until the processing thread drains.
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.processing_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for LiveCaptureStream {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.processing_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn build_input_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    queue: Arc<ArrayQueue<f32>>,
+) -> Result<cpal::Stream, LiveMonitorError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = config.channels as usize;
+    let error_callback = |err: cpal::StreamError| {
+        eprintln!("Live monitor input stream error: {err}");
+    };
+
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _info: &cpal::InputCallbackInfo| {
+                for frame in data.chunks(channels.max(1)) {
+                    let sum: f32 = frame.iter().map(|&s| f32::from_sample(s)).sum();
+                    let mono = sum / channels.max(1) as f32;
+                    let _ = queue.push(mono);
+                }
+            },
+            error_callback,
+            None,
+        )
+        .map_err(|e| LiveMonitorError::Stream(e.to_string()))
+}
+
+fn read_lufs(value: Result<f64, ebur128::Error>) -> f32 {
+    match value {
+        Ok(lufs) if lufs.is_finite() => (lufs as f32).clamp(LUFS_FLOOR, LUFS_CEILING),
+        _ => LUFS_FLOOR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_hop_reports_a_metrics_event_with_finite_lufs() {
+        let sample_rate = 48_000;
+        let hop_size = 2_048;
+        let mut monitor = LiveLoudnessSpectralMonitor::new(sample_rate, hop_size).unwrap();
+
+        let tone_hz = 1_000.0_f32;
+        let hop: Vec<f32> = (0..hop_size)
+            .map(|i| ((2.0 * std::f32::consts::PI * tone_hz * i as f32) / sample_rate as f32).sin())
+            .collect();
+
+        let event = monitor.process_hop(&hop).unwrap();
+        match event {
+            LiveMonitorEvent::Metrics {
+                momentary_lufs,
+                short_term_lufs,
+                spectral,
+            } => {
+                assert!(momentary_lufs.is_finite());
+                assert!(short_term_lufs.is_finite());
+                assert!((spectral.dialogue_centroid_hz - tone_hz).abs() < 250.0);
+            }
+            other => panic!("expected Metrics event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn zero_hop_size_is_rejected() {
+        assert!(matches!(
+            LiveLoudnessSpectralMonitor::new(48_000, 0),
+            Err(LiveMonitorError::InvalidHopSize)
+        ));
+    }
+}
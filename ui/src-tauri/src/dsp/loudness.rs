@@ -57,6 +57,10 @@ pub struct StemLoudnessMetrics {
     pub momentary_lufs: f32,
     pub short_term_lufs: f32,
     pub true_peak_dbtp: f32,
+    /// Raw digital sample peak (`20*log10(max|s|)` over the stem's raw buffer with no oversampling, same
+    /// `-120 dBTP` silence floor as `true_peak_dbtp`), cheaper than the 4x-cubic true peak and
+    /// useful for telling hard clipping apart from a merely-high inter-sample over.
+    pub sample_peak_dbtp: f32,
     pub crest_factor: f32,
 }
 
@@ -67,6 +71,24 @@ pub struct LoudnessMetrics {
     pub effects: StemLoudnessMetrics,
 }
 
+/// How [`LoudnessAnalyzer`]'s three per-stem meters accumulate integrated loudness and
+/// loudness range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoudnessAccumulationMode {
+    /// Keeps the full per-block energy history (ebur128's default `Mode::I | Mode::LRA`
+    /// behavior) for exact integrated/LRA results. Memory grows with stream duration, which
+    /// is fine for typical clips but can balloon across the three meters on a multi-hour
+    /// batch job.
+    #[default]
+    EnergyList,
+    /// Quantizes loudness values into fixed histogram bins (`Mode::HISTOGRAM`) instead of
+    /// keeping every block, so memory stays constant regardless of duration. Integrated
+    /// loudness and LRA are computed from the histogram and carry a small accuracy
+    /// trade-off (bin quantization) versus the energy-list mode — negligible for QC
+    /// purposes, but worth knowing about if chasing exact-to-the-bit compliance numbers.
+    Histogram,
+}
+
 #[derive(Debug)]
 pub struct LoudnessAnalyzer {
     sample_rate: u32,
@@ -77,11 +99,23 @@ pub struct LoudnessAnalyzer {
 
 impl LoudnessAnalyzer {
     pub fn new(sample_rate: u32) -> Result<Self, LoudnessError> {
+        Self::with_accumulation_mode(sample_rate, LoudnessAccumulationMode::default())
+    }
+
+    /// Like [`Self::new`], but lets the caller opt into [`LoudnessAccumulationMode::Histogram`]
+    /// to bound memory on long batch-analysis runs.
+    pub fn with_accumulation_mode(
+        sample_rate: u32,
+        accumulation_mode: LoudnessAccumulationMode,
+    ) -> Result<Self, LoudnessError> {
         if sample_rate == 0 {
             return Err(LoudnessError::InvalidSampleRate(sample_rate));
         }
 
-        let mode = Mode::M | Mode::S | Mode::I | Mode::LRA;
+        let mut mode = Mode::M | Mode::S | Mode::I | Mode::LRA;
+        if accumulation_mode == LoudnessAccumulationMode::Histogram {
+            mode |= Mode::HISTOGRAM;
+        }
         let dialogue_meter = EbuR128::new(1, sample_rate, mode)?;
         let music_meter = EbuR128::new(1, sample_rate, mode)?;
         let effects_meter = EbuR128::new(1, sample_rate, mode)?;
@@ -132,24 +166,113 @@ impl LoudnessAnalyzer {
                 momentary_lufs: read_lufs(self.dialogue_meter.loudness_momentary()),
                 short_term_lufs: read_lufs(self.dialogue_meter.loudness_shortterm()),
                 true_peak_dbtp: true_peak_dbtp_4x_cubic(&frame.dialogue_raw),
+                sample_peak_dbtp: sample_peak_dbtp(&frame.dialogue_raw),
                 crest_factor: crest_factor(&frame.dialogue_raw),
             },
             music: StemLoudnessMetrics {
                 momentary_lufs: read_lufs(self.music_meter.loudness_momentary()),
                 short_term_lufs: read_lufs(self.music_meter.loudness_shortterm()),
                 true_peak_dbtp: true_peak_dbtp_4x_cubic(&frame.music_raw),
+                sample_peak_dbtp: sample_peak_dbtp(&frame.music_raw),
                 crest_factor: crest_factor(&frame.music_raw),
             },
             effects: StemLoudnessMetrics {
                 momentary_lufs: read_lufs(self.effects_meter.loudness_momentary()),
                 short_term_lufs: read_lufs(self.effects_meter.loudness_shortterm()),
                 true_peak_dbtp: true_peak_dbtp_4x_cubic(&frame.effects_raw),
+                sample_peak_dbtp: sample_peak_dbtp(&frame.effects_raw),
                 crest_factor: crest_factor(&frame.effects_raw),
             },
         })
     }
 }
 
+/// Configuration for [`LoudnessNormalizer`]'s one-pass linear gain: where the mix should
+/// land (`loudness_target`, LUFS), the true-peak ceiling it must not exceed
+/// (`max_true_peak`, dBTP), and a manual trim (`offset`, dB) applied on top of the
+/// computed gain. `loudness_range_target` (LU) is carried through for callers reporting
+/// how far a stream's measured LRA sits from spec — the linear mode doesn't touch
+/// dynamics, so it isn't used to derive the gain itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizerSettings {
+    pub loudness_target: f64,
+    pub loudness_range_target: f64,
+    pub max_true_peak: f64,
+    pub offset: f64,
+}
+
+impl Default for NormalizerSettings {
+    /// EBU R128 / ATSC A/85 program delivery defaults: -24 LUFS, 7 LU range, -2 dBTP
+    /// ceiling, no manual trim.
+    fn default() -> Self {
+        Self {
+            loudness_target: -24.0,
+            loudness_range_target: 7.0,
+            max_true_peak: -2.0,
+            offset: 0.0,
+        }
+    }
+}
+
+/// One-pass linear loudness normalizer for the final mixed output, as a post-mix
+/// alternative to dialnorming each stem individually. Feed it every block of mixed audio
+/// via [`Self::observe`] as it's produced; once the meters have settled (typically once
+/// the whole stream has played through, the way `LoudnessAnalyzer::final_metrics` expects
+/// its own meters to be read), [`Self::linear_gain`] reports the single constant gain that
+/// pulls the mix to `NormalizerSettings::loudness_target` without the normalized true peak
+/// exceeding `NormalizerSettings::max_true_peak` — apply it before handing the interleaved
+/// buffer to `AudioOutputPlayer::push_interleaved_blocking`/`push_interleaved_nonblocking`.
+/// Unlike `MixerLoudnessTracker` in `dsp::mod`, which continuously ramps a correction while
+/// the mix plays, this is a single fixed gain computed from the stream's own final
+/// integrated loudness and peak — the dialnorm use case this request targets.
+#[derive(Debug)]
+pub struct LoudnessNormalizer {
+    settings: NormalizerSettings,
+    meter: EbuR128,
+    peak_dbtp: f32,
+}
+
+impl LoudnessNormalizer {
+    pub fn new(sample_rate: u32, settings: NormalizerSettings) -> Result<Self, LoudnessError> {
+        if sample_rate == 0 {
+            return Err(LoudnessError::InvalidSampleRate(sample_rate));
+        }
+        Ok(Self {
+            settings,
+            meter: EbuR128::new(1, sample_rate, Mode::I)?,
+            peak_dbtp: TRUE_PEAK_SILENCE_DBTP,
+        })
+    }
+
+    /// Feeds one block of the final mixed (mono) output into the integrated-loudness and
+    /// true-peak measurement ahead of a later `linear_gain()`/`apply()` call.
+    pub fn observe(&mut self, mixed: &[f32]) -> Result<(), LoudnessError> {
+        self.meter.add_frames_f32(mixed)?;
+        self.peak_dbtp = self.peak_dbtp.max(true_peak_dbtp_4x_cubic(mixed));
+        Ok(())
+    }
+
+    /// The constant linear gain [`Self::apply`] would use if called right now: pulls the
+    /// observed integrated loudness to `settings.loudness_target` (plus `settings.offset`),
+    /// clamped so the normalized true peak doesn't exceed `settings.max_true_peak`.
+    pub fn linear_gain(&self) -> f32 {
+        let integrated_lufs = read_lufs(self.meter.loudness_global()) as f64;
+        let target_gain_db =
+            self.settings.loudness_target - integrated_lufs + self.settings.offset;
+        let peak_headroom_db = self.settings.max_true_peak - self.peak_dbtp as f64;
+        let gain_db = target_gain_db.min(peak_headroom_db);
+        10f64.powf(gain_db / 20.0) as f32
+    }
+
+    /// Applies `linear_gain()` in place to an interleaved output buffer.
+    pub fn apply(&self, interleaved: &mut [f32]) {
+        let gain = self.linear_gain();
+        for sample in interleaved.iter_mut() {
+            *sample *= gain;
+        }
+    }
+}
+
 fn read_lu(value: Result<f64, ebur128::Error>) -> f32 {
     match value {
         Ok(v) if v.is_finite() && v >= 0.0 => v as f32,
@@ -164,6 +287,18 @@ fn read_lufs(value: Result<f64, ebur128::Error>) -> f32 {
     }
 }
 
+/// Raw digital sample peak in dBTP: `20*log10(max|s|)` over the buffer as-is, with no
+/// oversampling — cheaper than [`true_peak_dbtp_4x_cubic`] and blind to inter-sample overs,
+/// but useful for flagging hard clipping at the sample level.
+fn sample_peak_dbtp(samples: &[f32]) -> f32 {
+    let max_abs = samples.iter().copied().map(f32::abs).fold(0.0_f32, f32::max);
+    if max_abs <= EPSILON {
+        TRUE_PEAK_SILENCE_DBTP
+    } else {
+        20.0 * max_abs.log10()
+    }
+}
+
 fn crest_factor(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return 0.0;
@@ -249,4 +384,45 @@ mod tests {
         let dbtp = true_peak_dbtp_4x_cubic(&[0.0; 128]);
         assert_eq!(dbtp, TRUE_PEAK_SILENCE_DBTP);
     }
+
+    #[test]
+    fn normalizer_with_no_signal_chases_target_off_the_loudness_floor() {
+        let mut normalizer =
+            LoudnessNormalizer::new(48_000, NormalizerSettings::default()).unwrap();
+        normalizer.observe(&[0.0; 48_000]).unwrap();
+
+        // No real signal observed, so the meter reports the LUFS floor; the gain chases
+        // `loudness_target` off of that floor rather than refusing to correct.
+        let expected_gain_db = NormalizerSettings::default().loudness_target - LUFS_FLOOR as f64;
+        let gain_db = 20.0 * normalizer.linear_gain().log10() as f64;
+        assert!(
+            (gain_db - expected_gain_db).abs() < 0.5,
+            "expected ~{expected_gain_db} dB, got {gain_db} dB"
+        );
+    }
+
+    #[test]
+    fn normalizer_clamps_gain_to_the_true_peak_ceiling() {
+        let settings = NormalizerSettings {
+            loudness_target: 0.0,
+            max_true_peak: -1.0,
+            ..NormalizerSettings::default()
+        };
+        let mut normalizer = LoudnessNormalizer::new(48_000, settings).unwrap();
+
+        // A moderate-level tone, nowhere near clipping: boosting it all the way to 0 LUFS
+        // would want a larger gain than the -1 dBTP ceiling allows, so the peak limit (not
+        // the loudness target) should decide the applied gain.
+        let samples: Vec<f32> = (0..48_000)
+            .map(|i| ((i as f32) * 2.0 * std::f32::consts::PI / 100.0).sin() * 0.1)
+            .collect();
+        normalizer.observe(&samples).unwrap();
+
+        let gain_db = 20.0 * normalizer.linear_gain().log10() as f64;
+        let peak_headroom_db = settings.max_true_peak - true_peak_dbtp_4x_cubic(&samples) as f64;
+        assert!(
+            (gain_db - peak_headroom_db).abs() < 0.5,
+            "expected ~{peak_headroom_db} dB (peak-limited), got {gain_db} dB"
+        );
+    }
 }
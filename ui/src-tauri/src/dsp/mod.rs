@@ -1,29 +1,76 @@
+pub mod clocked_queue;
+pub mod live_monitor;
 pub mod loudness;
+pub mod onset;
 pub mod player;
+pub mod room_tone;
 pub mod scanner;
 pub mod spatial;
 pub mod spectral;
 
 use std::collections::{HashMap, VecDeque};
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+use ebur128::{EbuR128, Mode};
+use room_tone::RoomToneFiller;
 use symphonia::core::audio::{AudioBufferRef, SampleBuffer};
-use symphonia::core::codecs::{Decoder, DecoderOptions};
+use symphonia::core::codecs::{
+    CodecType, Decoder, DecoderOptions, CODEC_TYPE_AAC, CODEC_TYPE_FLAC, CODEC_TYPE_MP3,
+    CODEC_TYPE_PCM_F32BE, CODEC_TYPE_PCM_F32LE, CODEC_TYPE_PCM_F64BE, CODEC_TYPE_PCM_F64LE,
+    CODEC_TYPE_PCM_S16BE, CODEC_TYPE_PCM_S16LE, CODEC_TYPE_PCM_S24BE, CODEC_TYPE_PCM_S24LE,
+    CODEC_TYPE_PCM_S32BE, CODEC_TYPE_PCM_S32LE, CODEC_TYPE_PCM_S8, CODEC_TYPE_PCM_U8,
+    CODEC_TYPE_VORBIS,
+};
 use symphonia::core::errors::Error as SymphoniaError;
 use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
-use symphonia::core::io::MediaSourceStream;
+use symphonia::core::io::{MediaSource, MediaSourceStream};
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
-use symphonia::core::units::Time;
+use symphonia::core::units::{Time, TimeBase};
 use symphonia::default::{get_codecs, get_probe};
 
 const DEFAULT_TARGET_SAMPLE_RATE: u32 = 48_000;
 const DEFAULT_FRAME_SIZE: usize = 2048;
 const STEM_FADE_MS: f32 = 5.0;
+/// Default target integrated loudness for the mixer's loudness make-up gain, in LUFS. Kept
+/// distinct from `scanner::DEFAULT_TARGET_LUFS` (-24 LUFS, broadcast delivery) since this one
+/// is a live, continuously-updated correction rather than a one-shot offline normalization.
+const DEFAULT_LOUDNESS_TARGET_LUFS: f32 = -23.0;
+/// How long the make-up gain takes to ramp onto a new target once integrated loudness moves,
+/// in milliseconds. Much slower than `STEM_FADE_MS`'s per-event fades — this is correcting a
+/// slow-moving average level, not following solo/mute changes.
+const LOUDNESS_MAKEUP_RAMP_MS: f32 = 1_000.0;
+const LOUDNESS_MAKEUP_GAIN_LIMIT_DB: f32 = 12.0;
 const STEM_IDS: [&str; 5] = ["dx", "music", "sfx", "foley", "ambience"];
+/// Consecutive corrupt-packet `DecodeError`s `StemStreamDecoder::fill_until` tolerates
+/// before giving up on the stem. Isolated glitches are still skipped silently; a run this
+/// long means the asset itself is broken rather than having a stray bad packet.
+const MAX_DECODE_ERRORS: u32 = 50;
+
+/// Stem codecs `StemStreamDecoder::open` will decode: uncompressed PCM (WAV/AIFF) plus
+/// FLAC, Ogg Vorbis, MP3 and AAC, so a project can ship lossless dialogue alongside lossy
+/// music without every stem needing to be an uncompressed WAV.
+const SUPPORTED_STEM_CODECS: &[CodecType] = &[
+    CODEC_TYPE_PCM_S8,
+    CODEC_TYPE_PCM_U8,
+    CODEC_TYPE_PCM_S16LE,
+    CODEC_TYPE_PCM_S16BE,
+    CODEC_TYPE_PCM_S24LE,
+    CODEC_TYPE_PCM_S24BE,
+    CODEC_TYPE_PCM_S32LE,
+    CODEC_TYPE_PCM_S32BE,
+    CODEC_TYPE_PCM_F32LE,
+    CODEC_TYPE_PCM_F32BE,
+    CODEC_TYPE_PCM_F64LE,
+    CODEC_TYPE_PCM_F64BE,
+    CODEC_TYPE_FLAC,
+    CODEC_TYPE_VORBIS,
+    CODEC_TYPE_MP3,
+    CODEC_TYPE_AAC,
+];
 
 #[derive(Debug)]
 pub enum AudioDecodeError {
@@ -69,7 +116,7 @@ impl std::fmt::Display for AudioDecodeError {
             Self::Probe(msg) => write!(f, "Unable to probe audio stream: {msg}"),
             Self::UnsupportedFormat { stem, path, format } => write!(
                 f,
-                "Unsupported format for {stem} stem at {} (detected {format}, expected WAV)",
+                "Unsupported format for {stem} stem at {} (detected codec {format}, expected PCM/FLAC/Vorbis/MP3/AAC)",
                 path.display()
             ),
             Self::NoAudioTrack { stem, path } => {
@@ -117,10 +164,23 @@ impl From<std::io::Error> for AudioDecodeError {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy)]
 pub struct StemState {
     pub is_solo: bool,
     pub is_muted: bool,
+    /// Linear gain multiplier applied on top of the solo/mute mix, set via
+    /// `AudioCommand::SetStemGain`. Defaults to unity (no change in level).
+    pub gain: f32,
+}
+
+impl Default for StemState {
+    fn default() -> Self {
+        Self {
+            is_solo: false,
+            is_muted: false,
+            gain: 1.0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -165,23 +225,146 @@ pub fn shared_default_stem_states() -> SharedStemStates {
     Arc::new(RwLock::new(default_stem_states()))
 }
 
+/// Interpolation curve a [`GainRamp`] reshapes its 0..1 progress through. Perceived
+/// loudness is roughly logarithmic, so a fixed per-sample amplitude step can sound abrupt
+/// on mutes/solos; `EqualPower` and `Db` track perceived loudness more closely, the same
+/// way [`ResamplerQuality::Polyphase`] improved on a plain linear interpolator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GainRampShape {
+    /// Fixed per-sample amplitude step. Cheapest option; the only one that moved in a
+    /// straight line even though perceived loudness doesn't.
+    Linear,
+    /// Eases through a sin/cos quarter-curve — the same weighting a constant-power
+    /// crossfade gives its two signals — so the transition accelerates out of and into
+    /// its endpoints instead of moving at a constant rate.
+    #[default]
+    EqualPower,
+    /// Interpolates in the decibel domain and converts back to a linear multiplier via
+    /// `10f32.powf(db / 20.0)`, so the ramp advances at a constant *perceived* loudness
+    /// rate rather than a constant amplitude rate.
+    Db,
+}
+
+impl GainRampShape {
+    /// Floor used when a `Db`-shaped ramp's start or target gain is at or below zero,
+    /// since `20.0 * 0f32.log10()` is `-inf`. Chosen well below audibility so the ramp
+    /// still sweeps smoothly into silence instead of jumping at the last sample.
+    const DB_FLOOR: f32 = -100.0;
+
+    fn interpolate(self, start: f32, target: f32, progress: f32) -> f32 {
+        if progress >= 1.0 {
+            return target;
+        }
+        match self {
+            GainRampShape::Linear => start + (target - start) * progress,
+            GainRampShape::EqualPower => {
+                let angle = progress * std::f32::consts::FRAC_PI_2;
+                start * angle.cos() + target * angle.sin()
+            }
+            GainRampShape::Db => {
+                let to_db = |gain: f32| {
+                    if gain <= 0.0 {
+                        Self::DB_FLOOR
+                    } else {
+                        (20.0 * gain.log10()).max(Self::DB_FLOOR)
+                    }
+                };
+                let db = to_db(start) + (to_db(target) - to_db(start)) * progress;
+                10f32.powf(db / 20.0)
+            }
+        }
+    }
+}
+
+/// Chases a gain multiplier from its current value toward a target, reshaping the 0..1
+/// progress through `shape` instead of moving in a straight amplitude line. Anchors a
+/// `start`/`progress` pair whenever the target changes so a shaped curve (which needs to
+/// know how far through the current fade it is, not just the raw distance left) stays
+/// correct even if the target flips again mid-fade — e.g. un-muting while a mute fade is
+/// still running.
+#[derive(Debug, Clone, Copy)]
+struct GainRamp {
+    shape: GainRampShape,
+    step: f32,
+    start: f32,
+    target: f32,
+    progress: f32,
+    current: f32,
+}
+
+impl GainRamp {
+    fn new(initial_gain: f32, shape: GainRampShape, step: f32) -> Self {
+        Self {
+            shape,
+            step,
+            start: initial_gain,
+            target: initial_gain,
+            progress: 1.0,
+            current: initial_gain,
+        }
+    }
+
+    /// Converts a millisecond fade length to the per-sample `step` and builds the ramp.
+    fn from_fade_ms(initial_gain: f32, shape: GainRampShape, fade_ms: f32, sample_rate: u32) -> Self {
+        let fade_samples = ((sample_rate as f32 * fade_ms) / 1000.0).round().max(1.0);
+        Self::new(initial_gain, shape, 1.0 / fade_samples)
+    }
+
+    fn set_target(&mut self, target: f32) {
+        if target != self.target {
+            self.start = self.current;
+            self.target = target;
+            self.progress = 0.0;
+        }
+    }
+
+    /// Advances one sample toward `target` and returns the gain to apply to it.
+    fn tick(&mut self) -> f32 {
+        if self.progress < 1.0 {
+            self.progress = (self.progress + self.step).min(1.0);
+            self.current = self.shape.interpolate(self.start, self.target, self.progress);
+        }
+        self.current
+    }
+
+    fn apply_sample(&mut self, sample: f32) -> f32 {
+        sample * self.tick()
+    }
+
+    /// Ramps every sample in `buffer` in place; an empty buffer still snaps the ramp's
+    /// state to its target so a silent frame doesn't leave the next one resuming a stale
+    /// fade (matches the old `apply_gain_ramp`'s empty-buffer behavior).
+    fn apply(&mut self, buffer: &mut [f32]) {
+        if buffer.is_empty() {
+            self.start = self.target;
+            self.progress = 1.0;
+            self.current = self.target;
+            return;
+        }
+        for sample in buffer.iter_mut() {
+            *sample *= self.tick();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct StemRuntimeGains {
-    dx: f32,
-    music: f32,
-    foley: f32,
-    sfx: f32,
-    ambience: f32,
+    dx: GainRamp,
+    music: GainRamp,
+    foley: GainRamp,
+    sfx: GainRamp,
+    ambience: GainRamp,
 }
 
-impl Default for StemRuntimeGains {
-    fn default() -> Self {
+impl StemRuntimeGains {
+    fn new(shape: GainRampShape, step: f32) -> Self {
+        let ramp = || GainRamp::new(1.0, shape, step);
         Self {
-            dx: 1.0,
-            music: 1.0,
-            foley: 1.0,
-            sfx: 1.0,
-            ambience: 1.0,
+            dx: ramp(),
+            music: ramp(),
+            foley: ramp(),
+            sfx: ramp(),
+            ambience: ramp(),
         }
     }
 }
@@ -199,7 +382,7 @@ impl StemTargetGains {
     fn from_flags(flags: AudioFrameStemFlags) -> Self {
         let any_solo = flags.any_solo();
         let gain_for = |stem: StemState| -> f32 {
-            if any_solo {
+            let mix_gain = if any_solo {
                 if stem.is_solo {
                     1.0
                 } else {
@@ -209,7 +392,8 @@ impl StemTargetGains {
                 0.0
             } else {
                 1.0
-            }
+            };
+            mix_gain * stem.gain
         };
 
         Self {
@@ -226,13 +410,108 @@ impl StemTargetGains {
 pub struct AudioFrameStaticLoudness {
     pub dialogue_momentary_lufs: f32,
     pub dialogue_short_term_lufs: f32,
+    pub dialogue_integrated_lufs: f32,
     pub background_momentary_lufs: f32,
     pub background_short_term_lufs: f32,
+    pub background_integrated_lufs: f32,
+    /// Make-up gain (dB) the mixer's background ramp is chasing to pull
+    /// `background_integrated_lufs` toward `MikupAudioDecoder`'s loudness target, exposed so
+    /// callers/telemetry can show it without re-deriving it. `AudioFrame::background_raw`
+    /// gets it through a slow ramp, so the gain actually applied lags this value while the
+    /// ramp is moving.
+    pub background_makeup_gain_db: f32,
+}
+
+/// Tracks integrated/momentary/short-term loudness for the dialogue and background mix
+/// buses and derives the make-up gain that pulls the background bus toward `target_lufs`.
+/// Built on `ebur128` — the same K-weighting-pre-filter, 400ms-block, two-stage-gated
+/// BS.1770/EBU R128 algorithm `loudness::LoudnessAnalyzer` and `live_monitor` already use —
+/// rather than re-deriving the filter cascade here.
+#[derive(Debug)]
+struct MixerLoudnessTracker {
+    dialogue_meter: EbuR128,
+    background_meter: EbuR128,
+    target_lufs: f32,
+}
+
+impl MixerLoudnessTracker {
+    fn new(sample_rate: u32, target_lufs: f32) -> Result<Self, AudioDecodeError> {
+        let mode = Mode::M | Mode::S | Mode::I;
+        let meter_err =
+            |_: ebur128::Error| AudioDecodeError::InvalidConfig("failed to start loudness meter");
+        Ok(Self {
+            dialogue_meter: EbuR128::new(1, sample_rate, mode).map_err(meter_err)?,
+            background_meter: EbuR128::new(1, sample_rate, mode).map_err(meter_err)?,
+            target_lufs,
+        })
+    }
+
+    /// Feeds this frame's post-fade dialogue/background buffers to the meters and reports
+    /// where loudness stands now, without touching the audio itself — the caller applies
+    /// `background_makeup_gain_db()`'s gain separately via a [`GainRamp`].
+    fn process(&mut self, dialogue: &[f32], background: &[f32]) -> AudioFrameStaticLoudness {
+        let _ = self.dialogue_meter.add_frames_f32(dialogue);
+        let _ = self.background_meter.add_frames_f32(background);
+
+        AudioFrameStaticLoudness {
+            dialogue_momentary_lufs: read_lufs(self.dialogue_meter.loudness_momentary()),
+            dialogue_short_term_lufs: read_lufs(self.dialogue_meter.loudness_shortterm()),
+            dialogue_integrated_lufs: read_lufs(self.dialogue_meter.loudness_global()),
+            background_momentary_lufs: read_lufs(self.background_meter.loudness_momentary()),
+            background_short_term_lufs: read_lufs(self.background_meter.loudness_shortterm()),
+            background_integrated_lufs: read_lufs(self.background_meter.loudness_global()),
+            background_makeup_gain_db: self.background_makeup_gain_db(),
+        }
+    }
+
+    /// Gain (dB) to pull the background bus's integrated loudness so far toward
+    /// `target_lufs`, clamped to `LOUDNESS_MAKEUP_GAIN_LIMIT_DB` and `0.0` until the meter
+    /// has gated in enough audio to report a real integrated value (an early, mostly-silent
+    /// stream shouldn't snap to a wild correction).
+    fn background_makeup_gain_db(&self) -> f32 {
+        match self.background_meter.loudness_global() {
+            Ok(lufs) if lufs.is_finite() => {
+                let limit = LOUDNESS_MAKEUP_GAIN_LIMIT_DB;
+                (self.target_lufs - lufs as f32).clamp(-limit, limit)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+fn read_lufs(value: Result<f64, ebur128::Error>) -> f32 {
+    const LUFS_FLOOR: f32 = -70.0;
+    const LUFS_CEILING: f32 = 0.0;
+    match value {
+        Ok(lufs) if lufs.is_finite() => (lufs as f32).clamp(LUFS_FLOOR, LUFS_CEILING),
+        _ => LUFS_FLOOR,
+    }
+}
+
+/// Sample-accurate presentation timestamp for a [`SyncedAudioFrame`]: the output-sample
+/// count at `target_sample_rate` the frame's first sample plays at, same units
+/// `MikupAudioDecoder::position_seconds` already reports position in. A newtype rather
+/// than a raw `u64` so a `ClockedQueue` can order/compare frames without a caller
+/// accidentally comparing it to an unrelated sample count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct FrameClock(pub u64);
+
+impl FrameClock {
+    pub fn as_seconds(self, sample_rate: u32) -> f32 {
+        self.0 as f32 / sample_rate.max(1) as f32
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct AudioFrame {
     pub sample_rate: u32,
+    /// Interleaved sample count per frame for every `_raw` buffer below (e.g. `2` for
+    /// stereo). `1` — the default — means the buffers are the flat mono stream they've
+    /// always been; downstream loudness/dialogue-vs-background code assumes mono and
+    /// only the `spatial` module is expected to handle `channels > 1`.
+    pub channels: usize,
+    /// Presentation timestamp for this frame's first sample; see [`FrameClock`].
+    pub clock: FrameClock,
     pub dialogue_raw: Vec<f32>,
     pub background_raw: Vec<f32>,
     pub dx_raw: Vec<f32>,
@@ -248,6 +527,8 @@ impl Default for AudioFrame {
     fn default() -> Self {
         Self {
             sample_rate: 0,
+            channels: 1,
+            clock: FrameClock::default(),
             dialogue_raw: Vec::new(),
             background_raw: Vec::new(),
             dx_raw: Vec::new(),
@@ -262,8 +543,9 @@ impl Default for AudioFrame {
 }
 
 impl AudioFrame {
+    /// Frame count (i.e. per-channel sample count), not the raw buffer length.
     pub fn len(&self) -> usize {
-        self.dialogue_raw.len().min(self.background_raw.len())
+        (self.dialogue_raw.len().min(self.background_raw.len())) / self.channels.max(1)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -289,64 +571,476 @@ impl AudioFrame {
 
 pub type SyncedAudioFrame = AudioFrame;
 
-#[derive(Debug, Clone, Copy)]
-struct StreamingLinearResampler {
+/// Resampling strategy for [`StreamingResampler`]. Polyphase is the default for stem
+/// playback: first-order linear interpolation aliases audibly on non-trivial rate
+/// conversions (e.g. 44.1kHz stems played back at 48kHz). Linear remains available for
+/// callers that want the cheaper fast path and can tolerate the quality loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResamplerQuality {
+    Linear,
+    #[default]
+    Polyphase,
+}
+
+/// Upsample-by-`L`/downsample-by-`M` polyphase FIR resampler, or a first-order linear
+/// interpolator, sharing one `process`/`reset` surface so `StemStreamDecoder` doesn't
+/// need to care which a given stem was built with.
+#[derive(Debug, Clone)]
+struct StreamingResampler {
     input_rate: u32,
     output_rate: u32,
-    step: f64,
-    position: f64,
+    kind: ResamplerKind,
 }
 
-impl StreamingLinearResampler {
-    fn new(input_rate: u32, output_rate: u32) -> Self {
-        let step = input_rate as f64 / output_rate as f64;
+#[derive(Debug, Clone)]
+enum ResamplerKind {
+    Linear { step: f64, position: f64 },
+    Polyphase(PolyphaseResampler),
+}
+
+impl StreamingResampler {
+    fn new(input_rate: u32, output_rate: u32, quality: ResamplerQuality) -> Self {
+        let kind = match quality {
+            ResamplerQuality::Linear => ResamplerKind::Linear {
+                step: input_rate as f64 / output_rate as f64,
+                position: 0.0,
+            },
+            ResamplerQuality::Polyphase => {
+                ResamplerKind::Polyphase(PolyphaseResampler::new(input_rate, output_rate))
+            }
+        };
         Self {
             input_rate,
             output_rate,
-            step,
-            position: 0.0,
+            kind,
         }
     }
 
+    /// `source` is the caller-owned carry-over buffer (history the resampler still needs
+    /// plus not-yet-consumed input); `incoming` is appended before processing.
+    fn process(&mut self, source: &mut Vec<f32>, incoming: &[f32]) -> Vec<f32> {
+        match &mut self.kind {
+            ResamplerKind::Linear { step, position } => {
+                linear_process(source, incoming, *step, position)
+            }
+            ResamplerKind::Polyphase(resampler) => resampler.process(source, incoming),
+        }
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.input_rate == self.output_rate
+    }
+
+    fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    fn reset(&mut self) {
+        match &mut self.kind {
+            ResamplerKind::Linear { position, .. } => *position = 0.0,
+            ResamplerKind::Polyphase(resampler) => resampler.reset(),
+        }
+    }
+}
+
+fn linear_process(source: &mut Vec<f32>, incoming: &[f32], step: f64, position: &mut f64) -> Vec<f32> {
+    if incoming.is_empty() {
+        return Vec::new();
+    }
+
+    source.extend_from_slice(incoming);
+    if source.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut output = Vec::new();
+    while *position + 1.0 < source.len() as f64 {
+        let base = position.floor() as usize;
+        let frac = *position - (base as f64);
+        let current = source[base];
+        let next = source[base + 1];
+        output.push((current * (1.0 - frac as f32)) + (next * frac as f32));
+        *position += step;
+    }
+
+    let consumed = position.floor() as usize;
+    if consumed > 0 {
+        source.drain(0..consumed);
+        *position -= consumed as f64;
+    }
+
+    output
+}
+
+/// Taps per `max(L, M)` in the windowed-sinc low-pass prototype before it's split into `L`
+/// polyphase branches — higher means a sharper transition band/better stopband rejection
+/// at the cost of more multiply-adds per output sample.
+const POLYPHASE_TAP_MULTIPLIER: usize = 32;
+
+/// Band-limited rational resampler: upsamples by `L = output_rate/gcd`, low-pass filters
+/// at `0.5 / max(L, M)` (normalized to the upsampled rate) with a Blackman-windowed sinc,
+/// then downsamples by `M = input_rate/gcd`, all fused into `L` polyphase sub-filters so
+/// the zero-stuffed upsampled signal never actually needs to be materialized.
+#[derive(Debug, Clone)]
+struct PolyphaseResampler {
+    /// Upsample factor.
+    l: usize,
+    /// Downsample factor.
+    m: usize,
+    /// Coefficients per polyphase branch; `branches[p][k] = prototype[k * l + p]`.
+    branches: Vec<Vec<f32>>,
+    /// Read position relative to the start of `source`, in upsampled-rate units (`l` per
+    /// input sample): advances by `m` per output sample, and `position / l` is the input
+    /// index being read.
+    position: u64,
+}
+
+impl PolyphaseResampler {
+    fn new(input_rate: u32, output_rate: u32) -> Self {
+        let g = gcd(input_rate.max(1), output_rate.max(1));
+        let l = (output_rate / g).max(1) as usize;
+        let m = (input_rate / g).max(1) as usize;
+
+        // Prototype length is ~`POLYPHASE_TAP_MULTIPLIER * max(L, M)` taps, rounded up to a
+        // whole number of coefficients per branch so the split below is exact.
+        let max_lm = l.max(m);
+        let nominal_taps = POLYPHASE_TAP_MULTIPLIER * max_lm;
+        let branch_len = nominal_taps.div_ceil(l).max(1);
+        let total_taps = branch_len * l;
+        let cutoff = 0.5 / max_lm as f32;
+        let prototype = windowed_sinc_lowpass(total_taps, cutoff, l as f32);
+
+        let mut branches = vec![Vec::with_capacity(branch_len); l];
+        for (i, &coeff) in prototype.iter().enumerate() {
+            branches[i % l].push(coeff);
+        }
+
+        Self {
+            l,
+            m,
+            branches,
+            position: 0,
+        }
+    }
+
+    fn branch_len(&self) -> usize {
+        self.branches.first().map(Vec::len).unwrap_or(0)
+    }
+
+    /// `source` holds `branch_len() - 1` samples of history followed by whatever input
+    /// hasn't been consumed yet; `incoming` is appended before processing and the buffer
+    /// is trimmed back down to just the history the next call will need.
     fn process(&mut self, source: &mut Vec<f32>, incoming: &[f32]) -> Vec<f32> {
         if incoming.is_empty() {
             return Vec::new();
         }
-
         source.extend_from_slice(incoming);
-        if source.len() < 2 {
+
+        let branch_len = self.branch_len();
+        if branch_len == 0 {
             return Vec::new();
         }
 
         let mut output = Vec::new();
-        while self.position + 1.0 < source.len() as f64 {
-            let base = self.position.floor() as usize;
-            let frac = self.position - (base as f64);
-            let current = source[base];
-            let next = source[base + 1];
-            output.push((current * (1.0 - frac as f32)) + (next * frac as f32));
-            self.position += self.step;
+        loop {
+            let input_index = (self.position / self.l as u64) as usize;
+            let phase = (self.position % self.l as u64) as usize;
+
+            if input_index >= source.len() {
+                break;
+            }
+
+            // Taps that reach back before the first sample of the stream read it as silence.
+            let branch = &self.branches[phase];
+            let mut acc = 0.0_f32;
+            for (k, &coeff) in branch.iter().enumerate().take(input_index + 1) {
+                acc += coeff * source[input_index - k];
+            }
+            output.push(acc);
+            self.position += self.m as u64;
         }
 
-        let consumed = self.position.floor() as usize;
-        if consumed > 0 {
-            source.drain(0..consumed);
-            self.position -= consumed as f64;
+        // Keep only the trailing history the next call will need (branch_len - 1 samples
+        // ending at the last input index we might still reference).
+        let next_input_index = (self.position / self.l as u64) as usize;
+        let keep_from = next_input_index.saturating_sub(branch_len - 1);
+        if keep_from > 0 {
+            let drained = keep_from.min(source.len());
+            source.drain(0..drained);
+            self.position -= (drained * self.l) as u64;
         }
 
         output
     }
 
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Windowed-sinc low-pass prototype, `taps` long, with `normalized_cutoff` in cycles/sample
+/// and `gain` applied uniformly (the polyphase split needs gain `l` to preserve passband
+/// unity gain once the filter is shared across `l` zero-stuffed branches).
+fn windowed_sinc_lowpass(taps: usize, normalized_cutoff: f32, gain: f32) -> Vec<f32> {
+    if taps == 0 {
+        return Vec::new();
+    }
+    let center = (taps - 1) as f32 / 2.0;
+    (0..taps)
+        .map(|n| {
+            let x = n as f32 - center;
+            let sinc = if x.abs() < 1.0e-7 {
+                2.0 * normalized_cutoff
+            } else {
+                (2.0 * std::f32::consts::PI * normalized_cutoff * x).sin() / (std::f32::consts::PI * x)
+            };
+            sinc * blackman_window(n, taps) * gain
+        })
+        .collect()
+}
+
+fn blackman_window(n: usize, taps: usize) -> f32 {
+    if taps <= 1 {
+        return 1.0;
+    }
+    let x = n as f32 / (taps - 1) as f32;
+    0.42 - 0.5 * (2.0 * std::f32::consts::PI * x).cos() + 0.08 * (4.0 * std::f32::consts::PI * x).cos()
+}
+
+/// Interpolation kernel [`StemResampler`] reads between input samples with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StemResampleQuality {
+    #[default]
+    Linear,
+    /// 4-tap Lagrange interpolation — three extra multiply-adds per output sample over
+    /// `Linear`, for a noticeably cleaner high end on non-trivial rate ratios.
+    Lagrange4,
+}
+
+/// A fractional read position into a stem's sample stream: `ipos` is the last whole input
+/// sample consumed, `frac` is how far past it (in input samples, `[0, 1)`) the next output
+/// sample should be read from.
+#[derive(Debug, Clone, Copy, Default)]
+struct FracPos {
+    ipos: usize,
+    frac: f32,
+}
+
+/// Lightweight per-stem resampler that runs in the mixer right before gain ramping, so
+/// `sum_background_stems` and the dialogue path don't have to assume every stem buffer has
+/// already been converted to `target_sample_rate`. Unlike [`StreamingResampler`] — which
+/// converts a whole stem's stream once at decode time, with the caller owning the
+/// carry-over buffer — this one is sized to a mixer block and owns its own trailing
+/// history internally, so a stem arriving at a different native rate than its neighbors
+/// still lines up sample-for-sample once it reaches the mix.
+#[derive(Debug, Clone)]
+struct StemResampler {
+    /// `source_rate / target_rate`; advanced into `pos.frac` once per output sample.
+    step: f32,
+    pos: FracPos,
+    /// Carry-over input samples, `lookback_for(quality)` of which sit ahead of the logical
+    /// stream start as lookback for the active kernel (so e.g. `Lagrange4` always has a
+    /// sample behind the read position, even at the very start of the stream).
+    source: Vec<f32>,
+    quality: StemResampleQuality,
+}
+
+impl StemResampler {
+    fn new(source_rate: u32, target_rate: u32, quality: StemResampleQuality) -> Self {
+        let lookback = Self::lookback_for(quality);
+        Self {
+            step: source_rate as f32 / target_rate.max(1) as f32,
+            pos: FracPos {
+                ipos: lookback,
+                frac: 0.0,
+            },
+            source: vec![0.0; lookback],
+            quality,
+        }
+    }
+
+    fn lookback_for(quality: StemResampleQuality) -> usize {
+        match quality {
+            StemResampleQuality::Linear => 0,
+            StemResampleQuality::Lagrange4 => 1,
+        }
+    }
+
+    fn lookahead_for(quality: StemResampleQuality) -> usize {
+        match quality {
+            StemResampleQuality::Linear => 1,
+            StemResampleQuality::Lagrange4 => 2,
+        }
+    }
+
     fn is_passthrough(&self) -> bool {
-        self.input_rate == self.output_rate
+        (self.step - 1.0).abs() < f32::EPSILON
     }
 
-    fn output_rate(&self) -> u32 {
-        self.output_rate
+    /// `pos` is advanced one output sample at a time; on every call the consumed prefix of
+    /// `source` is dropped and `pos.ipos` carried back down to `lookback_for(quality)` so
+    /// the next call starts from the same
+    /// invariant this one did.
+    fn process(&mut self, incoming: Vec<f32>) -> Vec<f32> {
+        if self.is_passthrough() {
+            return incoming;
+        }
+        if incoming.is_empty() {
+            return Vec::new();
+        }
+
+        self.source.extend_from_slice(&incoming);
+        let lookback = Self::lookback_for(self.quality);
+        let lookahead = Self::lookahead_for(self.quality);
+
+        let mut output = Vec::new();
+        while self.pos.ipos + lookahead < self.source.len() {
+            let base = self.pos.ipos;
+            let sample = match self.quality {
+                StemResampleQuality::Linear => {
+                    let a = self.source[base];
+                    let b = self.source[base + 1];
+                    a + (b - a) * self.pos.frac
+                }
+                StemResampleQuality::Lagrange4 => lagrange4(
+                    self.source[base - 1],
+                    self.source[base],
+                    self.source[base + 1],
+                    self.source[base + 2],
+                    self.pos.frac,
+                ),
+            };
+            output.push(sample.clamp(-1.0, 1.0));
+
+            // Advance the fractional position, carrying whole units into `ipos`.
+            self.pos.frac += self.step;
+            let whole = self.pos.frac.floor();
+            self.pos.ipos += whole as usize;
+            self.pos.frac -= whole;
+        }
+
+        let consumed = self.pos.ipos.saturating_sub(lookback);
+        if consumed > 0 {
+            self.source.drain(0..consumed);
+            self.pos.ipos -= consumed;
+        }
+
+        output
     }
+}
 
-    fn reset(&mut self) {
-        self.position = 0.0;
+/// 4-tap (cubic) Lagrange interpolation through `y_m1, y0, y1, y2` — samples at input
+/// indices `-1, 0, 1, 2` — evaluated at `t` in `[0, 1)` between `y0` and `y1`.
+fn lagrange4(y_m1: f32, y0: f32, y1: f32, y2: f32, t: f32) -> f32 {
+    let c_m1 = -t * (t - 1.0) * (t - 2.0) / 6.0;
+    let c0 = (t + 1.0) * (t - 1.0) * (t - 2.0) / 2.0;
+    let c1 = -(t + 1.0) * t * (t - 2.0) / 2.0;
+    let c2 = (t + 1.0) * t * (t - 1.0) / 6.0;
+    y_m1 * c_m1 + y0 * c0 + y1 * c1 + y2 * c2
+}
+
+/// A byte-transform hook applied to data as it's read from a [`StemSource`]'s underlying
+/// `MediaSource`, e.g. to XOR-unwrap a lightly obfuscated asset before Symphonia's probe
+/// ever sees the plaintext. Takes the absolute byte offset the buffer starts at (stream
+/// ciphers typically key off position) and the bytes to transform in place.
+pub type StemSourceTransform = Arc<dyn Fn(u64, &mut [u8]) + Send + Sync>;
+
+/// A pluggable per-stem decode source: any `MediaSource` (an on-disk file, an in-memory
+/// buffer, a network stream, an encrypted reader, ...) paired with the format `Hint`
+/// Symphonia's probe uses to narrow down the container, a human-readable `label` used in
+/// error messages in place of a filesystem path, and an optional [`StemSourceTransform`].
+pub struct StemSource {
+    pub label: PathBuf,
+    pub source: Box<dyn MediaSource>,
+    pub hint: Hint,
+    pub transform: Option<StemSourceTransform>,
+}
+
+impl StemSource {
+    /// Builds a `StemSource` from an on-disk file, inferring the probe hint from the
+    /// extension the same way `StemStreamDecoder::open` always has.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AudioDecodeError> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+            hint.with_extension(ext);
+        }
+        Ok(Self {
+            label: path,
+            source: Box::new(file),
+            hint,
+            transform: None,
+        })
+    }
+
+    /// Builds a `StemSource` over an arbitrary `MediaSource` (memory-mapped archive entry,
+    /// network socket, encrypted reader, ...) with a caller-supplied label for diagnostics.
+    pub fn from_media_source(label: PathBuf, source: Box<dyn MediaSource>, hint: Hint) -> Self {
+        Self {
+            label,
+            source,
+            hint,
+            transform: None,
+        }
+    }
+
+    pub fn with_transform(mut self, transform: StemSourceTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+}
+
+/// Wraps a `MediaSource` so every byte Symphonia reads (and the absolute offset it reads
+/// at, since seeking can jump the position) first passes through a [`StemSourceTransform`].
+struct TransformedMediaSource {
+    inner: Box<dyn MediaSource>,
+    transform: StemSourceTransform,
+    position: u64,
+}
+
+impl TransformedMediaSource {
+    fn new(inner: Box<dyn MediaSource>, transform: StemSourceTransform) -> Self {
+        Self {
+            inner,
+            transform,
+            position: 0,
+        }
+    }
+}
+
+impl Read for TransformedMediaSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        (self.transform)(self.position, &mut buf[..n]);
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for TransformedMediaSource {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_position = self.inner.seek(pos)?;
+        self.position = new_position;
+        Ok(new_position)
+    }
+}
+
+impl MediaSource for TransformedMediaSource {
+    fn is_seekable(&self) -> bool {
+        self.inner.is_seekable()
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        self.inner.byte_len()
     }
 }
 
@@ -355,10 +1049,26 @@ struct StemStreamDecoder {
     format: Box<dyn FormatReader>,
     decoder: Box<dyn Decoder>,
     track_id: u32,
+    /// Interleaved by `output_channels` (e.g. `[L0, R0, L1, R1, ...]` for stereo); a mono
+    /// target makes this identical to the flat per-sample queue it always was.
     pending_samples: VecDeque<f32>,
-    resampler: StreamingLinearResampler,
-    resampler_source: Vec<f32>,
+    /// One resampler per output channel, each fed that channel's deinterleaved stream
+    /// independently — they share the same input/output rate so they stay in lockstep.
+    resamplers: Vec<StreamingResampler>,
+    resampler_sources: Vec<Vec<f32>>,
+    output_channels: usize,
     eof: bool,
+    /// Consecutive `DecodeError`s seen since the last successful decode; reset to 0 on
+    /// every packet that decodes cleanly. See `MAX_DECODE_ERRORS`.
+    consecutive_decode_errors: u32,
+    /// The track's native time base, used to convert a post-seek packet timestamp back
+    /// into seconds. `None` for tracks that don't report one (position then falls back to
+    /// the requested seek time).
+    time_base: Option<TimeBase>,
+    /// Real decoded position after the last `seek`, in seconds. Symphonia's accurate seek
+    /// lands on the packet boundary nearest the requested time rather than exactly on it,
+    /// so this is the truthful value rather than the caller's request.
+    decoded_position_secs: f32,
 }
 
 impl StemStreamDecoder {
@@ -366,6 +1076,8 @@ impl StemStreamDecoder {
         stem_name: &'static str,
         path: impl AsRef<Path>,
         target_sample_rate: u32,
+        resampler_quality: ResamplerQuality,
+        output_channels: usize,
     ) -> Result<Self, AudioDecodeError> {
         let path = path.as_ref().to_path_buf();
         if !path.exists() {
@@ -374,60 +1086,87 @@ impl StemStreamDecoder {
                 path,
             });
         }
-        if !looks_like_wav(&path)? {
-            return Err(AudioDecodeError::UnsupportedFormat {
-                stem: stem_name,
-                path: path.clone(),
-                format: "non-wav".to_string(),
-            });
-        }
-
-        let file = File::open(&path)?;
-        let source = MediaSourceStream::new(Box::new(file), Default::default());
+        let stem_source = StemSource::from_path(&path)?;
+        Self::open_from_source(
+            stem_name,
+            stem_source,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )
+    }
 
-        let extension = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or_default();
-        let mut hint = Hint::new();
-        if !extension.is_empty() {
-            hint.with_extension(extension);
-        }
+    /// Shared probe/codec-gate/decoder-build path for both on-disk stems (via [`Self::open`])
+    /// and arbitrary [`StemSource`]s (memory-mapped archives, network transports, encrypted
+    /// readers, ...), so a packaged asset pipeline doesn't need its own copy of this logic.
+    fn open_from_source(
+        stem_name: &'static str,
+        stem_source: StemSource,
+        target_sample_rate: u32,
+        resampler_quality: ResamplerQuality,
+        output_channels: usize,
+    ) -> Result<Self, AudioDecodeError> {
+        let StemSource {
+            label,
+            source,
+            hint,
+            transform,
+        } = stem_source;
+
+        let source: Box<dyn MediaSource> = match transform {
+            Some(transform) => Box::new(TransformedMediaSource::new(source, transform)),
+            None => source,
+        };
+        let mss = MediaSourceStream::new(source, Default::default());
 
+        // Trust Symphonia's probe to identify the container (WAV, AIFF, FLAC, Ogg, ...)
+        // rather than gating on a RIFF/WAVE header and the file extension up front.
         let probed = get_probe()
             .format(
                 &hint,
-                source,
+                mss,
                 &FormatOptions::default(),
                 &MetadataOptions::default(),
             )
             .map_err(|err| AudioDecodeError::Probe(err.to_string()))?;
 
         let format = probed.format;
-        if !extension.eq_ignore_ascii_case("wav") && !extension.eq_ignore_ascii_case("wave") {
-            return Err(AudioDecodeError::UnsupportedFormat {
-                stem: stem_name,
-                path: path.clone(),
-                format: extension.to_string(),
-            });
-        }
 
-        let (track_id, codec_params, sample_rate) = {
+        let (track_id, codec_params, sample_rate, time_base) = {
             let track = format
                 .default_track()
                 .ok_or_else(|| AudioDecodeError::NoAudioTrack {
                     stem: stem_name,
-                    path: path.clone(),
+                    path: label.clone(),
                 })?;
             let sample_rate = track.codec_params.sample_rate.ok_or_else(|| {
                 AudioDecodeError::MissingSampleRate {
                     stem: stem_name,
-                    path: path.clone(),
+                    path: label.clone(),
                 }
             })?;
-            (track.id, track.codec_params.clone(), sample_rate)
+            (
+                track.id,
+                track.codec_params.clone(),
+                sample_rate,
+                track.codec_params.time_base,
+            )
         };
 
+        // Gate on the codec actually found in the track, not the container or extension:
+        // a WAV can wrap non-PCM audio just as an Ogg can wrap something other than Vorbis.
+        if !SUPPORTED_STEM_CODECS.contains(&codec_params.codec) {
+            let format = get_codecs()
+                .get_codec(codec_params.codec)
+                .map(|descriptor| descriptor.short_name.to_string())
+                .unwrap_or_else(|| format!("{:?}", codec_params.codec));
+            return Err(AudioDecodeError::UnsupportedFormat {
+                stem: stem_name,
+                path: label,
+                format,
+            });
+        }
+
         let decoder = get_codecs()
             .make(&codec_params, &DecoderOptions::default())
             .map_err(|err| AudioDecodeError::Decode {
@@ -435,24 +1174,40 @@ impl StemStreamDecoder {
                 source: err,
             })?;
 
+        let output_channels = output_channels.max(1);
+        let resamplers = (0..output_channels)
+            .map(|_| StreamingResampler::new(sample_rate, target_sample_rate, resampler_quality))
+            .collect();
+        let resampler_sources = (0..output_channels).map(|_| Vec::new()).collect();
+
         Ok(Self {
             stem_name,
             format,
             decoder,
             track_id,
             pending_samples: VecDeque::new(),
-            resampler: StreamingLinearResampler::new(sample_rate, target_sample_rate),
-            resampler_source: Vec::new(),
+            resamplers,
+            resampler_sources,
+            output_channels,
             eof: false,
+            consecutive_decode_errors: 0,
+            time_base,
+            decoded_position_secs: 0.0,
         })
     }
 
     fn target_sample_rate(&self) -> u32 {
-        self.resampler.output_rate()
+        self.resamplers[0].output_rate()
     }
 
-    fn fill_until(&mut self, minimum_samples: usize) -> Result<(), AudioDecodeError> {
-        while self.pending_samples.len() < minimum_samples && !self.eof {
+    fn channels(&self) -> usize {
+        self.output_channels
+    }
+
+    /// `minimum_frames` is per-channel sample count (frames), matching `target_sample_rate`
+    /// and `frame_size` elsewhere in this module; `pending_samples` itself is interleaved.
+    fn fill_until(&mut self, minimum_frames: usize) -> Result<(), AudioDecodeError> {
+        while self.pending_samples.len() < minimum_frames * self.output_channels && !self.eof {
             let packet = match self.format.next_packet() {
                 Ok(packet) => packet,
                 Err(SymphoniaError::IoError(err))
@@ -479,8 +1234,17 @@ impl StemStreamDecoder {
 
             let decoded = match self.decoder.decode(&packet) {
                 Ok(decoded) => decoded,
-                Err(SymphoniaError::DecodeError(_)) => {
-                    // Corrupt packet: skip and continue processing the stream.
+                Err(SymphoniaError::DecodeError(err)) => {
+                    // Isolated corrupt packets are tolerated and skipped, same as today,
+                    // but a run of `MAX_DECODE_ERRORS` means the asset itself is broken
+                    // rather than having a stray bad packet, so stop spinning on it.
+                    self.consecutive_decode_errors += 1;
+                    if self.consecutive_decode_errors >= MAX_DECODE_ERRORS {
+                        return Err(AudioDecodeError::Decode {
+                            stem: self.stem_name,
+                            source: SymphoniaError::DecodeError(err),
+                        });
+                    }
                     continue;
                 }
                 Err(SymphoniaError::IoError(err))
@@ -496,25 +1260,50 @@ impl StemStreamDecoder {
                     });
                 }
             };
+            self.consecutive_decode_errors = 0;
 
-            let mono = decode_to_normalized_mono(decoded);
-            if mono.is_empty() {
+            let decoded_channels = decode_to_channels(decoded, self.output_channels);
+            if decoded_channels.is_empty() {
                 continue;
             }
 
-            if self.resampler.is_passthrough() {
-                self.pending_samples.extend(mono);
+            if self.resamplers[0].is_passthrough() {
+                self.pending_samples.extend(decoded_channels);
             } else {
-                let resampled = self.resampler.process(&mut self.resampler_source, &mono);
-                self.pending_samples.extend(resampled);
+                // Deinterleave, resample each channel against its own carry-over buffer
+                // (channels share the same input/output rate, so they stay in lockstep),
+                // then re-interleave before queuing.
+                let mut per_channel = Vec::with_capacity(self.output_channels);
+                for ch in 0..self.output_channels {
+                    let channel_samples: Vec<f32> = decoded_channels
+                        .iter()
+                        .skip(ch)
+                        .step_by(self.output_channels)
+                        .copied()
+                        .collect();
+                    let resampled = self.resamplers[ch]
+                        .process(&mut self.resampler_sources[ch], &channel_samples);
+                    per_channel.push(resampled);
+                }
+                let resampled_frames = per_channel.iter().map(Vec::len).min().unwrap_or(0);
+                for frame_idx in 0..resampled_frames {
+                    for channel in &per_channel {
+                        self.pending_samples.push_back(channel[frame_idx]);
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// `frame_size` is a per-channel (frame) count; `pending_samples` is interleaved by
+    /// `output_channels`, so the actual number of samples popped is `frame_size * output_channels`.
     fn pop_frame(&mut self, frame_size: usize) -> Vec<f32> {
-        let take = self.pending_samples.len().min(frame_size);
+        let take = self
+            .pending_samples
+            .len()
+            .min(frame_size * self.output_channels);
         self.pending_samples.drain(0..take).collect()
     }
 
@@ -528,11 +1317,17 @@ impl StemStreamDecoder {
 
     fn seek(&mut self, seconds: f32) -> Result<(), AudioDecodeError> {
         self.pending_samples.clear();
-        self.resampler_source.clear();
-        self.resampler.reset();
+        for source in &mut self.resampler_sources {
+            source.clear();
+        }
+        for resampler in &mut self.resamplers {
+            resampler.reset();
+        }
         self.eof = false;
+        self.consecutive_decode_errors = 0;
 
-        self.format
+        let seeked_to = self
+            .format
             .seek(
                 SeekMode::Accurate,
                 SeekTo::Time {
@@ -546,14 +1341,36 @@ impl StemStreamDecoder {
                 source,
             })?;
         self.decoder.reset();
+
+        // Accurate seek lands on the packet boundary nearest the requested time, not
+        // exactly on it, so report where we actually landed rather than echoing `seconds`.
+        self.decoded_position_secs = match self.time_base {
+            Some(time_base) => {
+                let time = time_base.calc_time(seeked_to.actual_ts);
+                time.seconds as f32 + time.frac as f32
+            }
+            None => seconds,
+        };
+
         Ok(())
     }
+
+    /// Real decoded position in seconds as of the last `seek`.
+    fn decoded_position_secs(&self) -> f32 {
+        self.decoded_position_secs
+    }
 }
 
-fn decode_to_normalized_mono(decoded: AudioBufferRef<'_>) -> Vec<f32> {
+/// Decodes one packet to `output_channels`-interleaved samples. `output_channels == 1`
+/// downmixes every source channel to mono, same as this decoder has always done. For
+/// `output_channels > 1`, the first `output_channels` source channels are carried through
+/// unmixed (so a stereo stem decoded at `output_channels == 2` yields true L/R); a source
+/// with fewer channels than requested (e.g. a mono stem decoded for stereo output)
+/// duplicates its last channel to fill the rest rather than leaving silence there.
+fn decode_to_channels(decoded: AudioBufferRef<'_>, output_channels: usize) -> Vec<f32> {
     let spec = *decoded.spec();
-    let channels = spec.channels.count();
-    if channels == 0 {
+    let source_channels = spec.channels.count();
+    if source_channels == 0 {
         return Vec::new();
     }
 
@@ -561,24 +1378,41 @@ fn decode_to_normalized_mono(decoded: AudioBufferRef<'_>) -> Vec<f32> {
     sample_buffer.copy_interleaved_ref(decoded);
     let interleaved = sample_buffer.samples();
 
-    interleaved
-        .chunks_exact(channels)
-        .map(|frame| {
-            let sum: f32 = frame.iter().copied().sum();
-            let mono = sum / channels as f32;
-            mono.clamp(-1.0, 1.0)
-        })
-        .collect()
-}
+    if output_channels == 1 {
+        return interleaved
+            .chunks_exact(source_channels)
+            .map(|frame| {
+                let sum: f32 = frame.iter().copied().sum();
+                let mono = sum / source_channels as f32;
+                mono.clamp(-1.0, 1.0)
+            })
+            .collect();
+    }
 
-fn looks_like_wav(path: &Path) -> Result<bool, AudioDecodeError> {
-    let mut file = File::open(path)?;
-    let mut header = [0_u8; 12];
-    let bytes_read = file.read(&mut header)?;
-    if bytes_read < header.len() {
-        return Ok(false);
+    let mut out = Vec::with_capacity((interleaved.len() / source_channels) * output_channels);
+    for frame in interleaved.chunks_exact(source_channels) {
+        for ch in 0..output_channels {
+            let sample = frame.get(ch).or_else(|| frame.last()).copied().unwrap_or(0.0);
+            out.push(sample.clamp(-1.0, 1.0));
+        }
     }
-    Ok(&header[0..4] == b"RIFF" && &header[8..12] == b"WAVE")
+    out
+}
+
+/// Whether the mixer replaces a silenced `ambience` stem (muted, solo'd out by another
+/// stem, or at zero gain) with synthesized room tone instead of the digital silence
+/// [`sum_background_stems`] would otherwise mix in under dialogue. An ambience stem that
+/// ends before its neighbors is zero-padded by `read_frame`/`drain_tail` before it reaches
+/// the mixer, so that stretch is mixed as silence and is not filled. Opt-in: `Off`
+/// reproduces today's zero-padding behavior exactly, the same default stance
+/// `StemResampleQuality` takes toward its upgraded-but-not-free option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbienceFillMode {
+    #[default]
+    Off,
+    /// Fill gaps with room tone synthesized by a [`RoomToneFiller`] from the stem's own
+    /// quietest recently-observed material.
+    RoomTone,
 }
 
 pub struct MikupAudioDecoder {
@@ -589,12 +1423,40 @@ pub struct MikupAudioDecoder {
     ambience: StemStreamDecoder,
     frame_size: usize,
     target_sample_rate: u32,
+    /// Interleaved sample count per frame every stem was decoded at; mirrors
+    /// `AudioFrame::channels` on every frame this decoder produces. `1` (mono) unless a
+    /// caller used [`Self::with_channels`] / [`Self::from_sources`] to request more.
+    output_channels: usize,
+    /// Per-stem mixer-side resamplers run in `process_frame`, right before gain ramping, so
+    /// the mix doesn't silently assume every stem buffer already arrived at
+    /// `target_sample_rate`. Each is built from that stem's own resolved decode rate, so
+    /// they're a no-op today (every stem is opened with the same `target_sample_rate`) but
+    /// stand ready for a stem pipeline that stops guaranteeing that upstream.
+    dx_resampler: StemResampler,
+    music_resampler: StemResampler,
+    foley_resampler: StemResampler,
+    sfx_resampler: StemResampler,
+    ambience_resampler: StemResampler,
     stem_states: SharedStemStates,
     stem_runtime_gains: StemRuntimeGains,
-    gain_step_per_sample: f32,
+    /// Measures dialogue/background integrated loudness and derives
+    /// `background_makeup_gain_ramp`'s target; see [`MixerLoudnessTracker`].
+    loudness_tracker: MixerLoudnessTracker,
+    /// Ramps the gain applied to the background sum to chase the make-up gain
+    /// `loudness_tracker` reports. Always `Db`-shaped, independent of `stem_runtime_gains`'
+    /// shape — it's correcting a dB-domain loudness measurement, not a solo/mute mix.
+    background_makeup_gain_ramp: GainRamp,
+    /// Continuously observes the real `ambience` stem and synthesizes room tone to fill
+    /// gaps in it, when opted into via [`AmbienceFillMode::RoomTone`]. `None` reproduces
+    /// today's zero-padding behavior exactly.
+    ambience_filler: Option<RoomToneFiller>,
     /// Set to true the first time a stem runs out of data while others still have samples,
     /// indicating the source stems have different durations and silence padding is active.
     pub alignment_mismatch_detected: bool,
+    /// Playback position in output samples at `target_sample_rate`: advanced by every frame
+    /// emitted via `read_frame`/`drain_tail`, and set by `seek` to where the dialogue stem
+    /// actually landed. Backs `position_seconds()`.
+    output_sample_position: u64,
 }
 
 impl MikupAudioDecoder {
@@ -607,6 +1469,84 @@ impl MikupAudioDecoder {
         stem_states: SharedStemStates,
         target_sample_rate: u32,
         frame_size: usize,
+    ) -> Result<Self, AudioDecodeError> {
+        Self::with_resampler_quality(
+            dx_path,
+            music_path,
+            foley_path,
+            sfx_path,
+            ambience_path,
+            stem_states,
+            target_sample_rate,
+            frame_size,
+            ResamplerQuality::Polyphase,
+        )
+    }
+
+    /// Same as [`Self::new`] but with an explicit resampling strategy — `Linear` trades
+    /// the band-limited polyphase resampler's quality for a cheaper fast path on stems
+    /// that don't need sample-rate conversion fidelity.
+    pub fn with_resampler_quality(
+        dx_path: impl AsRef<Path>,
+        music_path: impl AsRef<Path>,
+        foley_path: impl AsRef<Path>,
+        sfx_path: impl AsRef<Path>,
+        ambience_path: impl AsRef<Path>,
+        stem_states: SharedStemStates,
+        target_sample_rate: u32,
+        frame_size: usize,
+        resampler_quality: ResamplerQuality,
+    ) -> Result<Self, AudioDecodeError> {
+        Self::with_channels(
+            dx_path,
+            music_path,
+            foley_path,
+            sfx_path,
+            ambience_path,
+            stem_states,
+            target_sample_rate,
+            frame_size,
+            resampler_quality,
+            1,
+            StemResampleQuality::Linear,
+            DEFAULT_LOUDNESS_TARGET_LUFS,
+            GainRampShape::default(),
+            AmbienceFillMode::default(),
+        )
+    }
+
+    /// Same as [`Self::with_resampler_quality`] but takes an explicit `output_channels` per
+    /// stem instead of always downmixing to mono — e.g. `2` keeps true L/R content for the
+    /// `spatial` module instead of collapsing it. `output_channels == 1` reproduces today's
+    /// mono behavior exactly; every other consumer of this decoder (loudness, dialogue vs.
+    /// background mixing, ...) still assumes mono, so only pass more when the caller is
+    /// prepared to handle interleaved multichannel `AudioFrame` buffers. `mixer_resample_quality`
+    /// selects the interpolation kernel the per-stem mixer resamplers use (see
+    /// `StemResampler`); it's independent of `resampler_quality`, which governs the
+    /// decode-time conversion to `target_sample_rate`. `loudness_target_lufs` is the
+    /// integrated-loudness target `AudioFrame::static_loudness`'s make-up gain chases (see
+    /// `MixerLoudnessTracker`); pass `DEFAULT_LOUDNESS_TARGET_LUFS` for today's default.
+    /// `gain_ramp_shape` is the curve every stem's solo/mute/gain fade chases its target
+    /// through (see [`GainRampShape`]); the background make-up gain ramp always uses
+    /// `Db` regardless of this, since it's correcting a loudness measurement rather than a
+    /// mix-state change. `ambience_fill_mode` opts into papering over ambience gaps with
+    /// synthesized room tone instead of silence (see [`AmbienceFillMode`]); pass `Off` for
+    /// today's default.
+    pub fn with_channels(
+        dx_path: impl AsRef<Path>,
+        music_path: impl AsRef<Path>,
+        foley_path: impl AsRef<Path>,
+        sfx_path: impl AsRef<Path>,
+        ambience_path: impl AsRef<Path>,
+        stem_states: SharedStemStates,
+        target_sample_rate: u32,
+        frame_size: usize,
+        resampler_quality: ResamplerQuality,
+        output_channels: usize,
+        mixer_resample_quality: StemResampleQuality,
+        loudness_target_lufs: f32,
+        gain_ramp_shape: GainRampShape,
+        ambience_fill_mode: AmbienceFillMode,
     ) -> Result<Self, AudioDecodeError> {
         if target_sample_rate == 0 {
             return Err(AudioDecodeError::InvalidConfig(
@@ -617,12 +1557,164 @@ impl MikupAudioDecoder {
             return Err(AudioDecodeError::InvalidConfig("frame_size must be > 0"));
         }
 
-        let dx = StemStreamDecoder::open("dx_raw", dx_path, target_sample_rate)?;
-        let music = StemStreamDecoder::open("music_raw", music_path, target_sample_rate)?;
-        let foley = StemStreamDecoder::open("foley_raw", foley_path, target_sample_rate)?;
-        let sfx = StemStreamDecoder::open("sfx_raw", sfx_path, target_sample_rate)?;
-        let ambience = StemStreamDecoder::open("ambience_raw", ambience_path, target_sample_rate)?;
+        let dx = StemStreamDecoder::open(
+            "dx_raw",
+            dx_path,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let music = StemStreamDecoder::open(
+            "music_raw",
+            music_path,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let foley = StemStreamDecoder::open(
+            "foley_raw",
+            foley_path,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let sfx = StemStreamDecoder::open(
+            "sfx_raw",
+            sfx_path,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let ambience = StemStreamDecoder::open(
+            "ambience_raw",
+            ambience_path,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+
+        Self::assemble(
+            dx,
+            music,
+            foley,
+            sfx,
+            ambience,
+            stem_states,
+            target_sample_rate,
+            frame_size,
+            mixer_resample_quality,
+            loudness_target_lufs,
+            gain_ramp_shape,
+            ambience_fill_mode,
+        )
+    }
 
+    /// Same as [`Self::new`] / [`Self::with_resampler_quality`] but takes a [`StemSource`]
+    /// per stem instead of a filesystem path — an archive entry, a network stream, an
+    /// encrypted reader with an XOR/stream-cipher [`StemSourceTransform`] applied, etc. This
+    /// is the entry point for packaged-asset pipelines that don't ship five loose WAV files.
+    /// Like [`Self::with_channels`], `output_channels == 1` downmixes to mono (today's
+    /// behavior); a higher value preserves interleaved multichannel content instead.
+    /// `mixer_resample_quality`, `loudness_target_lufs`, `gain_ramp_shape` and
+    /// `ambience_fill_mode` are the same mixer resampler, loudness-target, fade-curve and
+    /// ambience-fill knobs described on [`Self::with_channels`].
+    pub fn from_sources(
+        dx: StemSource,
+        music: StemSource,
+        foley: StemSource,
+        sfx: StemSource,
+        ambience: StemSource,
+        stem_states: SharedStemStates,
+        target_sample_rate: u32,
+        frame_size: usize,
+        resampler_quality: ResamplerQuality,
+        output_channels: usize,
+        mixer_resample_quality: StemResampleQuality,
+        loudness_target_lufs: f32,
+        gain_ramp_shape: GainRampShape,
+        ambience_fill_mode: AmbienceFillMode,
+    ) -> Result<Self, AudioDecodeError> {
+        if target_sample_rate == 0 {
+            return Err(AudioDecodeError::InvalidConfig(
+                "target_sample_rate must be > 0",
+            ));
+        }
+        if frame_size == 0 {
+            return Err(AudioDecodeError::InvalidConfig("frame_size must be > 0"));
+        }
+
+        let dx = StemStreamDecoder::open_from_source(
+            "dx_raw",
+            dx,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let music = StemStreamDecoder::open_from_source(
+            "music_raw",
+            music,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let foley = StemStreamDecoder::open_from_source(
+            "foley_raw",
+            foley,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let sfx = StemStreamDecoder::open_from_source(
+            "sfx_raw",
+            sfx,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+        let ambience = StemStreamDecoder::open_from_source(
+            "ambience_raw",
+            ambience,
+            target_sample_rate,
+            resampler_quality,
+            output_channels,
+        )?;
+
+        Self::assemble(
+            dx,
+            music,
+            foley,
+            sfx,
+            ambience,
+            stem_states,
+            target_sample_rate,
+            frame_size,
+            mixer_resample_quality,
+            loudness_target_lufs,
+            gain_ramp_shape,
+            ambience_fill_mode,
+        )
+    }
+
+    /// Shared post-open assembly for every constructor: checks all five stems resolved to
+    /// the same output sample rate, builds the per-stem mixer resamplers (a no-op today
+    /// since every stem above was opened against the same `target_sample_rate`, but real
+    /// machinery for a stem pipeline that stops guaranteeing that), and builds the
+    /// gain-ramp/fade state, the loudness tracker/make-up gain ramp, and (if opted into)
+    /// the ambience-fill subsystem.
+    fn assemble(
+        dx: StemStreamDecoder,
+        music: StemStreamDecoder,
+        foley: StemStreamDecoder,
+        sfx: StemStreamDecoder,
+        ambience: StemStreamDecoder,
+        stem_states: SharedStemStates,
+        target_sample_rate: u32,
+        frame_size: usize,
+        mixer_resample_quality: StemResampleQuality,
+        loudness_target_lufs: f32,
+        gain_ramp_shape: GainRampShape,
+        ambience_fill_mode: AmbienceFillMode,
+    ) -> Result<Self, AudioDecodeError> {
         let resolved_sample_rate = dx.target_sample_rate();
         if music.target_sample_rate() != resolved_sample_rate
             || foley.target_sample_rate() != resolved_sample_rate
@@ -634,9 +1726,44 @@ impl MikupAudioDecoder {
             ));
         }
 
-        let fade_samples = ((target_sample_rate as f32 * STEM_FADE_MS) / 1000.0)
+        let output_channels = dx.channels();
+        if music.channels() != output_channels
+            || foley.channels() != output_channels
+            || sfx.channels() != output_channels
+            || ambience.channels() != output_channels
+        {
+            return Err(AudioDecodeError::InvalidConfig(
+                "stems resolved to mismatched output channel counts",
+            ));
+        }
+
+        let make_resampler = |stem: &StemStreamDecoder| {
+            StemResampler::new(
+                stem.target_sample_rate(),
+                target_sample_rate,
+                mixer_resample_quality,
+            )
+        };
+        let dx_resampler = make_resampler(&dx);
+        let music_resampler = make_resampler(&music);
+        let foley_resampler = make_resampler(&foley);
+        let sfx_resampler = make_resampler(&sfx);
+        let ambience_resampler = make_resampler(&ambience);
+
+        let gain_step_per_sample = 1.0 / ((target_sample_rate as f32 * STEM_FADE_MS) / 1000.0)
             .round()
             .max(1.0);
+        let loudness_tracker = MixerLoudnessTracker::new(target_sample_rate, loudness_target_lufs)?;
+        let background_makeup_gain_ramp = GainRamp::from_fade_ms(
+            1.0,
+            GainRampShape::Db,
+            LOUDNESS_MAKEUP_RAMP_MS,
+            target_sample_rate,
+        );
+        let ambience_filler = match ambience_fill_mode {
+            AmbienceFillMode::Off => None,
+            AmbienceFillMode::RoomTone => Some(RoomToneFiller::new()),
+        };
 
         Ok(Self {
             dx,
@@ -646,10 +1773,19 @@ impl MikupAudioDecoder {
             ambience,
             frame_size,
             target_sample_rate,
+            output_channels,
+            dx_resampler,
+            music_resampler,
+            foley_resampler,
+            sfx_resampler,
+            ambience_resampler,
             stem_states,
-            stem_runtime_gains: StemRuntimeGains::default(),
-            gain_step_per_sample: 1.0 / fade_samples,
+            stem_runtime_gains: StemRuntimeGains::new(gain_ramp_shape, gain_step_per_sample),
+            loudness_tracker,
+            background_makeup_gain_ramp,
+            ambience_filler,
             alignment_mismatch_detected: false,
+            output_sample_position: 0,
         })
     }
 
@@ -680,6 +1816,13 @@ impl MikupAudioDecoder {
         self.frame_size
     }
 
+    /// Sample-accurate playback position in seconds: the position the last `seek` landed on
+    /// plus the output samples emitted via `read_frame`/`drain_tail` since, divided by
+    /// `target_sample_rate`.
+    pub fn position_seconds(&self) -> f32 {
+        self.output_sample_position as f32 / self.target_sample_rate as f32
+    }
+
     /// Reads a synchronized frame for all stems.
     /// Returns `Ok(None)` when all stems are fully consumed.
     pub fn read_frame(&mut self) -> Result<Option<SyncedAudioFrame>, AudioDecodeError> {
@@ -719,12 +1862,14 @@ impl MikupAudioDecoder {
                 return Ok(None);
             }
 
-            // If one stem has no decodable data for this step, keep stream alignment with silence.
-            dx = vec![0.0; self.frame_size];
-            music = vec![0.0; self.frame_size];
-            foley = vec![0.0; self.frame_size];
-            sfx = vec![0.0; self.frame_size];
-            ambience = vec![0.0; self.frame_size];
+            // If one stem has no decodable data for this step, keep stream alignment with
+            // silence (`frame_size` frames, each `output_channels` samples wide).
+            let silent_frame = vec![0.0; self.frame_size * self.output_channels];
+            dx = silent_frame.clone();
+            music = silent_frame.clone();
+            foley = silent_frame.clone();
+            sfx = silent_frame.clone();
+            ambience = silent_frame;
         }
 
         let max_len = dx
@@ -751,7 +1896,9 @@ impl MikupAudioDecoder {
         sfx.resize(max_len, 0.0);
         ambience.resize(max_len, 0.0);
 
-        Ok(Some(self.process_frame(dx, music, foley, sfx, ambience)))
+        let clock = FrameClock(self.output_sample_position);
+        self.output_sample_position += (max_len / self.output_channels) as u64;
+        Ok(Some(self.process_frame(clock, dx, music, foley, sfx, ambience)))
     }
 
     pub fn drain_tail(&mut self) -> SyncedAudioFrame {
@@ -773,7 +1920,9 @@ impl MikupAudioDecoder {
         sfx.resize(max_len, 0.0);
         ambience.resize(max_len, 0.0);
 
-        self.process_frame(dx, music, foley, sfx, ambience)
+        let clock = FrameClock(self.output_sample_position);
+        self.output_sample_position += (max_len / self.output_channels) as u64;
+        self.process_frame(clock, dx, music, foley, sfx, ambience)
     }
 
     pub fn seek(&mut self, seconds: f32) -> Result<(), AudioDecodeError> {
@@ -787,38 +1936,70 @@ impl MikupAudioDecoder {
         self.foley.seek(seconds)?;
         self.sfx.seek(seconds)?;
         self.ambience.seek(seconds)?;
+
+        // Report where the dialogue stem actually landed, not the requested time, since
+        // accurate seek only guarantees the nearest packet boundary.
+        self.output_sample_position =
+            (self.dx.decoded_position_secs() * self.target_sample_rate as f32).round() as u64;
         Ok(())
     }
 
     fn process_frame(
         &mut self,
-        mut dx: Vec<f32>,
-        mut music: Vec<f32>,
-        mut foley: Vec<f32>,
-        mut sfx: Vec<f32>,
-        mut ambience: Vec<f32>,
+        clock: FrameClock,
+        dx: Vec<f32>,
+        music: Vec<f32>,
+        foley: Vec<f32>,
+        sfx: Vec<f32>,
+        ambience: Vec<f32>,
     ) -> SyncedAudioFrame {
+        // Mixer-side resampling runs before gain ramping, so a stem that reaches the mix at
+        // something other than `target_sample_rate` still lines up with its neighbors. A
+        // no-op today (every stem above was opened against the same `target_sample_rate`),
+        // and only correct for mono/`output_channels == 1` framing — it treats the buffer
+        // as one flat stream, so it would resample across channel boundaries if run on
+        // interleaved multichannel output.
+        let mut dx = self.dx_resampler.process(dx);
+        let mut music = self.music_resampler.process(music);
+        let mut foley = self.foley_resampler.process(foley);
+        let mut sfx = self.sfx_resampler.process(sfx);
+        let mut ambience = self.ambience_resampler.process(ambience);
+
         let stem_flags = self.snapshot_stem_flags();
         let target_gains = StemTargetGains::from_flags(stem_flags);
 
-        apply_gain_ramp(
-            &mut dx,
-            &mut self.stem_runtime_gains.dx,
-            target_gains.dx,
-            self.gain_step_per_sample,
-        );
-        let background = sum_background_stems(
+        // Feed the filler real ambience audio regardless of mute state — decoding isn't
+        // muted, only the mixed output is, so there's still genuine material to learn from.
+        if let Some(filler) = self.ambience_filler.as_mut() {
+            filler.observe(&ambience);
+        }
+
+        self.stem_runtime_gains.dx.set_target(target_gains.dx);
+        self.stem_runtime_gains.dx.apply(&mut dx);
+        let mut background = sum_background_stems(
             &mut music,
             &mut foley,
             &mut sfx,
             &mut ambience,
             &mut self.stem_runtime_gains,
             target_gains,
-            self.gain_step_per_sample,
+            self.ambience_filler.as_mut(),
         );
 
+        // Loudness is measured on the post-fade dialogue/background buffers, before the
+        // make-up gain below is applied to them — the tracker needs the content's natural
+        // level to know how far off target it is. Only correct for mono/`output_channels
+        // == 1` framing, same caveat as the mixer resamplers above.
+        let static_loudness = self.loudness_tracker.process(&dx, &background);
+        let makeup_target_gain =
+            10f32.powf(static_loudness.background_makeup_gain_db / 20.0);
+        self.background_makeup_gain_ramp.set_target(makeup_target_gain);
+        self.background_makeup_gain_ramp.apply(&mut background);
+
         SyncedAudioFrame {
             sample_rate: self.target_sample_rate,
+            channels: self.output_channels,
+            clock,
             dialogue_raw: dx.clone(),
             background_raw: background,
             dx_raw: dx,
@@ -827,7 +2008,7 @@ impl MikupAudioDecoder {
             sfx_raw: sfx,
             ambience_raw: ambience,
             stem_flags,
-            static_loudness: None,
+            static_loudness: Some(static_loudness),
         }
     }
 
@@ -840,23 +2021,11 @@ impl MikupAudioDecoder {
     }
 }
 
-fn apply_gain_ramp(buffer: &mut [f32], current_gain: &mut f32, target_gain: f32, step: f32) {
-    if buffer.is_empty() {
-        *current_gain = target_gain;
-        return;
-    }
-
-    for sample in buffer.iter_mut() {
-        let delta = target_gain - *current_gain;
-        if delta.abs() <= step {
-            *current_gain = target_gain;
-        } else {
-            *current_gain += step * delta.signum();
-        }
-        *sample *= *current_gain;
-    }
-}
-
+/// Sums the four background stems after running each through its `runtime_gains` ramp.
+/// `ambience_filler`, when present and fed enough material, replaces the ambience
+/// contribution to the mix (though not `ambience`'s own buffer — see below) whenever it
+/// would otherwise be digital silence: the stem muted/solo'd-out (`target_gains.ambience`
+/// at or below zero) or exhausted before its neighbors (`i` past `ambience`'s length).
 fn sum_background_stems(
     music: &mut [f32],
     foley: &mut [f32],
@@ -864,8 +2033,13 @@ fn sum_background_stems(
     ambience: &mut [f32],
     runtime_gains: &mut StemRuntimeGains,
     target_gains: StemTargetGains,
-    gain_step_per_sample: f32,
+    ambience_filler: Option<&mut RoomToneFiller>,
 ) -> Vec<f32> {
+    runtime_gains.music.set_target(target_gains.music);
+    runtime_gains.foley.set_target(target_gains.foley);
+    runtime_gains.sfx.set_target(target_gains.sfx);
+    runtime_gains.ambience.set_target(target_gains.ambience);
+
     let len = music
         .len()
         .max(foley.len())
@@ -873,63 +2047,54 @@ fn sum_background_stems(
         .max(ambience.len());
     let mut mixed = vec![0.0; len];
 
+    let ambience_gapped = target_gains.ambience <= 0.0;
+    let room_tone = match ambience_filler {
+        Some(filler) if filler.has_material() => Some(filler.synthesize(len)),
+        _ => None,
+    };
+
     for (i, mixed_sample) in mixed.iter_mut().enumerate() {
-        let music_sample = apply_gain_step(
-            music.get(i).copied().unwrap_or(0.0),
-            &mut runtime_gains.music,
-            target_gains.music,
-            gain_step_per_sample,
-        );
+        let music_sample = runtime_gains
+            .music
+            .apply_sample(music.get(i).copied().unwrap_or(0.0));
         if let Some(slot) = music.get_mut(i) {
             *slot = music_sample;
         }
 
-        let foley_sample = apply_gain_step(
-            foley.get(i).copied().unwrap_or(0.0),
-            &mut runtime_gains.foley,
-            target_gains.foley,
-            gain_step_per_sample,
-        );
+        let foley_sample = runtime_gains
+            .foley
+            .apply_sample(foley.get(i).copied().unwrap_or(0.0));
         if let Some(slot) = foley.get_mut(i) {
             *slot = foley_sample;
         }
 
-        let sfx_sample = apply_gain_step(
-            sfx.get(i).copied().unwrap_or(0.0),
-            &mut runtime_gains.sfx,
-            target_gains.sfx,
-            gain_step_per_sample,
-        );
+        let sfx_sample = runtime_gains
+            .sfx
+            .apply_sample(sfx.get(i).copied().unwrap_or(0.0));
         if let Some(slot) = sfx.get_mut(i) {
             *slot = sfx_sample;
         }
 
-        let ambience_sample = apply_gain_step(
-            ambience.get(i).copied().unwrap_or(0.0),
-            &mut runtime_gains.ambience,
-            target_gains.ambience,
-            gain_step_per_sample,
-        );
+        let ambience_sample = runtime_gains
+            .ambience
+            .apply_sample(ambience.get(i).copied().unwrap_or(0.0));
         if let Some(slot) = ambience.get_mut(i) {
             *slot = ambience_sample;
         }
 
-        *mixed_sample = music_sample + foley_sample + sfx_sample + ambience_sample;
+        // `ambience_raw` above still reports the real (muted-to-zero, if applicable) stem
+        // signal; only the audible mix gets the synthesized substitute.
+        let ambience_contribution = match &room_tone {
+            Some(tone) if ambience_gapped || i >= ambience.len() => tone[i],
+            _ => ambience_sample,
+        };
+
+        *mixed_sample = music_sample + foley_sample + sfx_sample + ambience_contribution;
     }
 
     mixed
 }
 
-fn apply_gain_step(sample: f32, current_gain: &mut f32, target_gain: f32, step: f32) -> f32 {
-    let delta = target_gain - *current_gain;
-    if delta.abs() <= step {
-        *current_gain = target_gain;
-    } else {
-        *current_gain += step * delta.signum();
-    }
-    sample * *current_gain
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -941,18 +2106,22 @@ mod tests {
             music: StemState {
                 is_solo: false,
                 is_muted: false,
+                gain: 1.0,
             },
             foley: StemState {
                 is_solo: false,
                 is_muted: false,
+                gain: 1.0,
             },
             sfx: StemState {
                 is_solo: true,
                 is_muted: true,
+                gain: 1.0,
             },
             ambience: StemState {
                 is_solo: false,
                 is_muted: false,
+                gain: 1.0,
             },
         };
         let gains = StemTargetGains::from_flags(flags);
@@ -965,11 +2134,201 @@ mod tests {
     }
 
     #[test]
-    fn gain_step_moves_toward_target_without_jumps() {
-        let mut gain = 1.0_f32;
-        let step = 0.1_f32;
-        let sample = apply_gain_step(1.0, &mut gain, 0.0, step);
+    fn linear_gain_ramp_moves_toward_target_without_jumps() {
+        let mut ramp = GainRamp::new(1.0, GainRampShape::Linear, 0.1);
+        ramp.set_target(0.0);
+        let sample = ramp.apply_sample(1.0);
         assert!((sample - 0.9).abs() < 1e-6);
-        assert!((gain - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn equal_power_ramp_reaches_exact_target() {
+        let mut ramp = GainRamp::new(0.0, GainRampShape::EqualPower, 0.25);
+        ramp.set_target(1.0);
+        for _ in 0..3 {
+            ramp.tick();
+        }
+        let last = ramp.tick();
+        assert!((last - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn db_ramp_toward_silence_fades_out_monotonically() {
+        let mut ramp = GainRamp::new(1.0, GainRampShape::Db, 0.2);
+        ramp.set_target(0.0);
+        let mut previous = 1.0;
+        for _ in 0..5 {
+            let current = ramp.tick();
+            assert!(current <= previous + 1e-6);
+            previous = current;
+        }
+        assert!((previous - 0.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn equal_rate_polyphase_resampler_is_passthrough() {
+        let resampler = StreamingResampler::new(48_000, 48_000, ResamplerQuality::Polyphase);
+        assert!(resampler.is_passthrough());
+    }
+
+    #[test]
+    fn polyphase_resampler_roughly_preserves_a_dc_signal() {
+        let mut resampler = StreamingResampler::new(44_100, 48_000, ResamplerQuality::Polyphase);
+        let mut source = Vec::new();
+        let input = vec![0.5_f32; 10_000];
+        let output = resampler.process(&mut source, &input);
+
+        // Settled output (past the filter's initial group delay) should sit close to the
+        // constant input level; a linear or broken filter would drift off 0.5 instead.
+        let settled: Vec<f32> = output.into_iter().skip(200).collect();
+        assert!(!settled.is_empty());
+        for sample in settled {
+            assert!(
+                (sample - 0.5).abs() < 0.05,
+                "expected ~0.5, got {sample}"
+            );
+        }
+    }
+
+    #[test]
+    fn polyphase_resampler_outputs_roughly_the_expected_sample_count() {
+        let mut resampler = StreamingResampler::new(44_100, 48_000, ResamplerQuality::Polyphase);
+        let mut source = Vec::new();
+        let input = vec![0.0_f32; 44_100];
+        let output = resampler.process(&mut source, &input);
+
+        // One second of 44.1kHz input should yield close to one second of 48kHz output,
+        // modulo the FIR filter's group delay (roughly branch_len/2 input samples).
+        let expected = 48_000_i64;
+        assert!(
+            (output.len() as i64 - expected).abs() < 200,
+            "expected ~{expected} samples, got {}",
+            output.len()
+        );
+    }
+
+    #[test]
+    fn equal_rate_stem_resampler_is_passthrough() {
+        let mut resampler = StemResampler::new(48_000, 48_000, StemResampleQuality::Linear);
+        let input = vec![0.1, 0.2, 0.3, 0.4];
+        assert_eq!(resampler.process(input.clone()), input);
+    }
+
+    #[test]
+    fn stem_resampler_roughly_preserves_a_dc_signal() {
+        for quality in [StemResampleQuality::Linear, StemResampleQuality::Lagrange4] {
+            let mut resampler = StemResampler::new(44_100, 48_000, quality);
+            let input = vec![0.5_f32; 4_000];
+            let output = resampler.process(input);
+
+            let settled: Vec<f32> = output.into_iter().skip(10).collect();
+            assert!(!settled.is_empty());
+            for sample in settled {
+                assert!(
+                    (sample - 0.5).abs() < 0.01,
+                    "{quality:?}: expected ~0.5, got {sample}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stem_resampler_carries_fractional_position_across_calls() {
+        // Same input, split into one call vs. two back-to-back calls, should land on the
+        // same output — proof that `FracPos` and the trailing history are actually carried
+        // across calls rather than each call restarting from a fresh position.
+        let input: Vec<f32> = (0..2_000).map(|i| (i as f32 * 0.01).sin()).collect();
+
+        let mut whole = StemResampler::new(44_100, 48_000, StemResampleQuality::Lagrange4);
+        let whole_output = whole.process(input.clone());
+
+        let mut split = StemResampler::new(44_100, 48_000, StemResampleQuality::Lagrange4);
+        let mut split_output = split.process(input[..1_000].to_vec());
+        split_output.extend(split.process(input[1_000..].to_vec()));
+
+        assert_eq!(whole_output.len(), split_output.len());
+        for (a, b) in whole_output.iter().zip(split_output.iter()) {
+            assert!((a - b).abs() < 1e-6, "expected {a}, got {b}");
+        }
+    }
+
+    /// Minimal seekable `MediaSource` over an in-memory buffer, standing in for a real
+    /// memory-mapped/network/encrypted reader so `TransformedMediaSource` can be driven
+    /// through a non-trivial `Read`/`Seek` implementation rather than just a `Vec<u8>`.
+    struct CursorMediaSource(std::io::Cursor<Vec<u8>>);
+
+    impl Read for CursorMediaSource {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Seek for CursorMediaSource {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.0.seek(pos)
+        }
+    }
+
+    impl MediaSource for CursorMediaSource {
+        fn is_seekable(&self) -> bool {
+            true
+        }
+
+        fn byte_len(&self) -> Option<u64> {
+            Some(self.0.get_ref().len() as u64)
+        }
+    }
+
+    /// Offset-dependent, involutive (XOR) keystream standing in for a real stream-cipher
+    /// transform — applying it twice at the same absolute offset recovers the original
+    /// bytes, so it doubles as both the "encryption" used to build fixture data and the
+    /// `StemSourceTransform` under test.
+    fn xor_keystream(offset: u64, buf: &mut [u8]) {
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte ^= ((offset + i as u64) & 0xFF) as u8;
+        }
+    }
+
+    #[test]
+    fn transformed_media_source_tracks_absolute_offset_across_split_reads() {
+        let plaintext: Vec<u8> = (0..64u8).collect();
+        let mut ciphertext = plaintext.clone();
+        xor_keystream(0, &mut ciphertext);
+
+        let inner: Box<dyn MediaSource> = Box::new(CursorMediaSource(std::io::Cursor::new(ciphertext)));
+        let transform: StemSourceTransform = Arc::new(xor_keystream);
+        let mut source = TransformedMediaSource::new(inner, transform);
+
+        // Split across two reads at a boundary that doesn't line up with any natural
+        // chunk size, so a bug that resets the offset per `read()` call (rather than
+        // carrying `position` across them) would desync the keystream partway through.
+        let mut first = vec![0u8; 20];
+        source.read_exact(&mut first).unwrap();
+        let mut second = vec![0u8; 44];
+        source.read_exact(&mut second).unwrap();
+
+        let mut recovered = first;
+        recovered.extend(second);
+        assert_eq!(recovered, plaintext);
+    }
+
+    #[test]
+    fn transformed_media_source_recomputes_offset_after_a_seek() {
+        let plaintext: Vec<u8> = (0..64u8).collect();
+        let mut ciphertext = plaintext.clone();
+        xor_keystream(0, &mut ciphertext);
+
+        let inner: Box<dyn MediaSource> = Box::new(CursorMediaSource(std::io::Cursor::new(ciphertext)));
+        let transform: StemSourceTransform = Arc::new(xor_keystream);
+        let mut source = TransformedMediaSource::new(inner, transform);
+
+        // Jump straight to byte 32 without reading anything before it — if `position`
+        // isn't updated from the inner seek's returned offset, the transform would keep
+        // using offset 0 and this would come back as garbage instead of `plaintext[32..]`.
+        source.seek(SeekFrom::Start(32)).unwrap();
+
+        let mut tail = vec![0u8; 32];
+        source.read_exact(&mut tail).unwrap();
+        assert_eq!(tail, plaintext[32..]);
     }
 }
@@ -0,0 +1,252 @@
+//! Spectral-flux onset detection over the 1-4 kHz speech band, used to segment where a
+//! dialogue stem actually has someone talking rather than silence or music bleed-through.
+//!
+//! Shares the windowing/FFT approach `SpectralAnalyzer` and `StemFeatureExtractor` use, but
+//! restricts the flux calculation to the speech band and normalizes by that band's own
+//! energy (rather than `SpectralAnalyzer::dialogue_flux`'s whole-spectrum, level-dependent
+//! value), since onset picking needs a bounded signal to threshold against.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use realfft::num_complex::Complex32;
+use realfft::{RealFftPlanner, RealToComplex};
+
+use crate::dsp::spectral::{fill_window_buffer, hann_window, SPEECH_HIGH_HZ, SPEECH_LOW_HZ};
+
+const EPSILON: f32 = 1.0e-12;
+/// Hop size the detector windows and FFTs at, matching `StemFeatureExtractor`'s hop.
+const ONSET_HOP_SIZE: usize = 2_048;
+/// How many preceding hops the adaptive threshold's local median is computed over.
+const MEDIAN_WINDOW_HOPS: usize = 9;
+/// Added atop the local median flux to decide whether a hop counts as active/a peak.
+const PEAK_PICK_DELTA: f32 = 0.08;
+/// Onsets closer together than this are folded into the same attack (debounces
+/// tremolo/vibrato re-triggering the picker within a single syllable).
+const MIN_INTER_ONSET_SECS: f32 = 0.1;
+
+/// A contiguous stretch, in seconds from stream start, where speech-band flux stayed above
+/// the adaptive threshold.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct DialogueActivitySegment {
+    pub start_secs: f32,
+    pub end_secs: f32,
+}
+
+/// Hop-at-a-time onset/activity detector: tracks a half-wave-rectified, energy-normalized
+/// spectral flux function over the speech band, peak-picks onsets against a local-median
+/// adaptive threshold with a minimum inter-onset interval, and reports contiguous
+/// above-threshold stretches as [`DialogueActivitySegment`]s.
+pub struct DialogueActivityDetector {
+    sample_rate: u32,
+    r2c: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    prev_band_magnitudes: Option<Vec<f32>>,
+    recent_flux: VecDeque<f32>,
+    elapsed_secs: f32,
+    last_onset_secs: Option<f32>,
+    onset_timestamps: Vec<f32>,
+    active_segment_start: Option<f32>,
+    segments: Vec<DialogueActivitySegment>,
+}
+
+impl DialogueActivityDetector {
+    pub fn new(sample_rate: u32) -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let r2c = planner.plan_fft_forward(ONSET_HOP_SIZE);
+        let window = hann_window(ONSET_HOP_SIZE);
+
+        Self {
+            sample_rate,
+            input: r2c.make_input_vec(),
+            spectrum: r2c.make_output_vec(),
+            r2c,
+            window,
+            prev_band_magnitudes: None,
+            recent_flux: VecDeque::with_capacity(MEDIAN_WINDOW_HOPS),
+            elapsed_secs: 0.0,
+            last_onset_secs: None,
+            onset_timestamps: Vec::new(),
+            active_segment_start: None,
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn hop_size(&self) -> usize {
+        ONSET_HOP_SIZE
+    }
+
+    /// Onset timestamps (seconds from stream start) picked so far.
+    pub fn onset_timestamps(&self) -> &[f32] {
+        &self.onset_timestamps
+    }
+
+    /// Processes one hop (`hop_size()` samples; a short final hop should be zero-padded by
+    /// the caller), advances the internal clock by `hop_size() / sample_rate` seconds, and
+    /// returns whether this hop fell inside an active (above-threshold) stretch.
+    pub fn process_hop(&mut self, samples: &[f32]) -> bool {
+        let hop_start_secs = self.elapsed_secs;
+        self.elapsed_secs += self.hop_size() as f32 / self.sample_rate as f32;
+
+        fill_window_buffer(&mut self.input, samples, &self.window);
+        let _ = self.r2c.process(&mut self.input, &mut self.spectrum);
+        let magnitudes: Vec<f32> = self.spectrum.iter().map(|c| c.norm()).collect();
+        let band = speech_band_slice(&magnitudes, self.sample_rate);
+
+        let flux = speech_band_flux(&band, self.prev_band_magnitudes.as_deref());
+        self.prev_band_magnitudes = Some(band);
+
+        let threshold = local_median(&self.recent_flux) + PEAK_PICK_DELTA;
+        let is_active = flux > threshold;
+
+        let since_last_onset = self
+            .last_onset_secs
+            .map(|t| hop_start_secs - t)
+            .unwrap_or(f32::MAX);
+        if is_active && since_last_onset >= MIN_INTER_ONSET_SECS {
+            self.onset_timestamps.push(hop_start_secs);
+            self.last_onset_secs = Some(hop_start_secs);
+        }
+
+        match (is_active, self.active_segment_start) {
+            (true, None) => self.active_segment_start = Some(hop_start_secs),
+            (false, Some(start)) => {
+                self.segments.push(DialogueActivitySegment {
+                    start_secs: start,
+                    end_secs: hop_start_secs,
+                });
+                self.active_segment_start = None;
+            }
+            _ => {}
+        }
+
+        if self.recent_flux.len() == MEDIAN_WINDOW_HOPS {
+            self.recent_flux.pop_front();
+        }
+        self.recent_flux.push_back(flux);
+
+        is_active
+    }
+
+    /// Closes any still-open activity segment at the current clock position and returns the
+    /// full segment list accumulated so far.
+    pub fn finish(&mut self) -> Vec<DialogueActivitySegment> {
+        if let Some(start) = self.active_segment_start.take() {
+            self.segments.push(DialogueActivitySegment {
+                start_secs: start,
+                end_secs: self.elapsed_secs,
+            });
+        }
+        std::mem::take(&mut self.segments)
+    }
+}
+
+/// Half-wave-rectified sum of positive magnitude differences between consecutive hops,
+/// normalized by the L2 norm of the current hop's band magnitudes (the square root of its
+/// total band energy) so the result is roughly bounded
+/// regardless of signal level. Zero on the first hop (no history yet).
+fn speech_band_flux(band_magnitudes: &[f32], prev_band_magnitudes: Option<&[f32]>) -> f32 {
+    let Some(prev) = prev_band_magnitudes else {
+        return 0.0;
+    };
+    if band_magnitudes.is_empty() || prev.len() != band_magnitudes.len() {
+        return 0.0;
+    }
+
+    let band_energy: f32 = band_magnitudes.iter().map(|m| m * m).sum();
+    if band_energy <= EPSILON {
+        return 0.0;
+    }
+
+    let positive_diff_sum: f32 = band_magnitudes
+        .iter()
+        .zip(prev.iter())
+        .map(|(&curr, &prior)| (curr - prior).max(0.0))
+        .sum();
+
+    positive_diff_sum / band_energy.sqrt()
+}
+
+fn speech_band_slice(magnitudes: &[f32], sample_rate: u32) -> Vec<f32> {
+    if magnitudes.is_empty() {
+        return Vec::new();
+    }
+
+    let fft_size = (magnitudes.len().saturating_sub(1) * 2).max(1);
+    let hz_per_bin = sample_rate as f32 / fft_size as f32;
+    if hz_per_bin <= EPSILON {
+        return Vec::new();
+    }
+
+    let mut start = (SPEECH_LOW_HZ / hz_per_bin).floor() as usize;
+    let mut end = (SPEECH_HIGH_HZ / hz_per_bin).ceil() as usize;
+    if start >= magnitudes.len() {
+        start = magnitudes.len().saturating_sub(1);
+    }
+    if end >= magnitudes.len() {
+        end = magnitudes.len().saturating_sub(1);
+    }
+    if end < start {
+        return Vec::new();
+    }
+
+    magnitudes[start..=end].to_vec()
+}
+
+fn local_median(values: &VecDeque<f32>) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted: Vec<f32> = values.iter().copied().collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted[sorted.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone_hop(hop_index: usize, hop_size: usize, sample_rate: u32, hz: f32) -> Vec<f32> {
+        (0..hop_size)
+            .map(|i| {
+                let t = (hop_index * hop_size + i) as f32;
+                (2.0 * std::f32::consts::PI * hz * t / sample_rate as f32).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn silence_produces_no_onsets_or_segments() {
+        let sample_rate = 48_000;
+        let mut detector = DialogueActivityDetector::new(sample_rate);
+
+        for _ in 0..20 {
+            detector.process_hop(&vec![0.0; detector.hop_size()]);
+        }
+
+        assert!(detector.onset_timestamps().is_empty());
+        assert!(detector.finish().is_empty());
+    }
+
+    #[test]
+    fn a_tone_appearing_mid_stream_opens_an_activity_segment() {
+        let sample_rate = 48_000;
+        let hop_size = ONSET_HOP_SIZE;
+        let mut detector = DialogueActivityDetector::new(sample_rate);
+
+        for _ in 0..6 {
+            detector.process_hop(&vec![0.0; hop_size]);
+        }
+        for i in 6..20 {
+            detector.process_hop(&tone_hop(i, hop_size, sample_rate, 2_000.0));
+        }
+
+        assert!(!detector.onset_timestamps().is_empty());
+        let segments = detector.finish();
+        assert!(!segments.is_empty());
+        assert!(segments[0].start_secs > 0.0);
+    }
+}
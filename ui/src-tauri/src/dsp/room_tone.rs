@@ -0,0 +1,185 @@
+//! Ambience-filler subsystem used by `MikupAudioDecoder` to paper over a silenced
+//! `ambience` stem (muted or solo'd out) with believable room tone synthesized from the
+//! stem's own material, instead of the jarring digital silence `sum_background_stems` would
+//! otherwise mix in under dialogue. The decoder zero-pads an ambience stem that ends before
+//! its neighbors, so that stretch reaches the mixer as real silent samples and is not filled.
+//!
+//! Works by chunking the real incoming ambience signal into fixed ~1024-sample windows,
+//! keeping a short rolling history of them by RMS, and synthesizing fill audio by looping
+//! through the quietest of those chunks (the ones most likely to be genuine low-level room
+//! tone rather than a one-off event) with short equal-power crossfades at the seams so the
+//! loop doesn't click.
+
+use std::collections::VecDeque;
+
+/// Chunk size real ambience audio is grouped into before its RMS is measured. ~21ms at
+/// 48kHz — short enough to isolate a quiet stretch from a nearby loud one, long enough for
+/// the RMS to mean something.
+const CHUNK_SIZE: usize = 1024;
+/// How many recent chunks are kept to choose fill material from.
+const HISTORY_CAPACITY: usize = 20;
+/// How many of the kept chunks (lowest RMS first) the filler loops through when
+/// synthesizing — narrow enough to stay consistently quiet, wide enough not to loop the
+/// exact same clip every time.
+const QUIETEST_POOL: usize = 5;
+/// Equal-power crossfade length at each loop seam. ~1.3ms at 48kHz: enough to mask a
+/// splice without being audible as its own fade.
+const CROSSFADE_SAMPLES: usize = 64;
+
+#[derive(Debug, Clone)]
+struct AmbienceChunk {
+    samples: Vec<f32>,
+    rms: f32,
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
+    (sum_sq / samples.len() as f32).sqrt()
+}
+
+/// Crossfades `chunk` onto the tail of `out` (equal-power, see [`GainRampShape::EqualPower`](
+/// crate::dsp::GainRampShape::EqualPower) for the same curve used elsewhere) instead of
+/// concatenating it raw, so looping back through a handful of chunks doesn't click at every
+/// seam.
+fn append_with_crossfade(out: &mut Vec<f32>, chunk: &[f32]) {
+    if chunk.is_empty() {
+        return;
+    }
+    if out.is_empty() {
+        out.extend_from_slice(chunk);
+        return;
+    }
+
+    let fade = CROSSFADE_SAMPLES.min(out.len()).min(chunk.len());
+    let tail_start = out.len() - fade;
+    for i in 0..fade {
+        let progress = (i + 1) as f32 / (fade + 1) as f32;
+        let angle = progress * std::f32::consts::FRAC_PI_2;
+        out[tail_start + i] = out[tail_start + i] * angle.cos() + chunk[i] * angle.sin();
+    }
+    out.extend_from_slice(&chunk[fade..]);
+}
+
+/// Continuously observes the real ambience stem in fixed chunks and, on request,
+/// synthesizes a believable loop of room tone from the quietest material it has seen so
+/// far. See the module docs for the overall approach.
+#[derive(Debug, Clone)]
+pub struct RoomToneFiller {
+    history: VecDeque<AmbienceChunk>,
+    pending: Vec<f32>,
+    next_pool_index: usize,
+    /// Synthesized samples past the end of the last `synthesize` call's output, carried
+    /// over so the next call continues from them (and crossfades its next chunk onto them)
+    /// instead of jumping to a new spot in the loop at the frame boundary.
+    tail: Vec<f32>,
+}
+
+impl Default for RoomToneFiller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoomToneFiller {
+    pub fn new() -> Self {
+        Self {
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
+            pending: Vec::with_capacity(CHUNK_SIZE),
+            next_pool_index: 0,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Feeds real ambience samples in for RMS-chunk tracking. Call this with the stem's
+    /// actual decoded audio regardless of mute state — decoding isn't muted, only the
+    /// mixed output is, so there's still genuine material to learn from.
+    pub fn observe(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            self.pending.push(sample);
+            if self.pending.len() == CHUNK_SIZE {
+                let chunk_rms = rms(&self.pending);
+                if self.history.len() >= HISTORY_CAPACITY {
+                    self.history.pop_front();
+                }
+                self.history.push_back(AmbienceChunk {
+                    samples: std::mem::replace(&mut self.pending, Vec::with_capacity(CHUNK_SIZE)),
+                    rms: chunk_rms,
+                });
+            }
+        }
+    }
+
+    /// Whether enough real ambience has been observed to synthesize fill from.
+    pub fn has_material(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    fn quietest_pool(&self) -> Vec<&[f32]> {
+        let mut ranked: Vec<&AmbienceChunk> = self.history.iter().collect();
+        ranked.sort_by(|a, b| a.rms.partial_cmp(&b.rms).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+            .into_iter()
+            .take(QUIETEST_POOL)
+            .map(|chunk| chunk.samples.as_slice())
+            .collect()
+    }
+
+    /// Synthesizes `len` samples of room tone by looping through the quietest observed
+    /// chunks with crossfaded seams. Returns silence if nothing has been observed yet.
+    pub fn synthesize(&mut self, len: usize) -> Vec<f32> {
+        if len == 0 || self.history.is_empty() {
+            return vec![0.0; len];
+        }
+
+        let pool = self.quietest_pool();
+        let mut out = std::mem::take(&mut self.tail);
+        while out.len() < len {
+            let chunk = pool[self.next_pool_index % pool.len()];
+            self.next_pool_index = self.next_pool_index.wrapping_add(1);
+            append_with_crossfade(&mut out, chunk);
+        }
+
+        self.tail = out.split_off(len);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_material_synthesizes_silence() {
+        let mut filler = RoomToneFiller::new();
+        assert!(!filler.has_material());
+        assert_eq!(filler.synthesize(10), vec![0.0; 10]);
+    }
+
+    #[test]
+    fn synthesizes_requested_length_from_observed_material() {
+        let mut filler = RoomToneFiller::new();
+        filler.observe(&vec![0.01; CHUNK_SIZE * 3]);
+        assert!(filler.has_material());
+
+        let tone = filler.synthesize(5_000);
+        assert_eq!(tone.len(), 5_000);
+    }
+
+    #[test]
+    fn prefers_quieter_chunks_over_louder_ones() {
+        let mut filler = RoomToneFiller::new();
+        // A loud chunk followed by several quiet ones: the quiet pool should dominate a
+        // long synthesis, so its average amplitude should sit far below the loud chunk's.
+        filler.observe(&vec![0.9; CHUNK_SIZE]);
+        for _ in 0..4 {
+            filler.observe(&vec![0.01; CHUNK_SIZE]);
+        }
+
+        let tone = filler.synthesize(CHUNK_SIZE * 4);
+        let mean_abs: f32 = tone.iter().map(|s| s.abs()).sum::<f32>() / tone.len() as f32;
+        assert!(mean_abs < 0.1, "expected quiet fill, got mean abs {mean_abs}");
+    }
+}
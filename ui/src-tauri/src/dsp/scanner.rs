@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
 use ebur128::{EbuR128, Mode};
@@ -13,9 +13,27 @@ use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 use symphonia::default::{get_codecs, get_probe};
 
+use crate::dsp::onset::{DialogueActivityDetector, DialogueActivitySegment};
+use crate::dsp::player::MonoResampler;
+use crate::dsp::spectral::{StemFeatureExtractor, StemFeatureVector};
+
+/// Only the DX stem gets dialogue-activity gating even when `with_dialogue_activity_gating`
+/// is on — the onset detector is tuned for speech, not music or effects beds.
+const DIALOGUE_ACTIVITY_STEM: &str = "DX";
+
 const LUFS_FLOOR: f32 = -70.0;
 const LUFS_CEILING: f32 = 0.0;
 const STEM_SCAN_PROGRESS_INTERVAL_SECS: f32 = 5.0;
+/// Caps how many stems decode concurrently — unbounded fan-out would saturate disk I/O
+/// and codec thread pools on machines with few cores even though only 3-5 stems exist.
+const MAX_CONCURRENT_STEM_SCANS: usize = 3;
+const TRUE_PEAK_SILENCE_DBTP: f32 = -120.0;
+const TRUE_PEAK_EPSILON: f64 = 1.0e-12;
+/// Default integrated-loudness target for `gain_to_target_db` when the caller doesn't
+/// request a specific one — US broadcast dialnorm convention (EBU R128 proper uses -23).
+const DEFAULT_TARGET_LUFS: f32 = -24.0;
+/// `gain_to_target_db` never proposes a gain that would push true peak above this ceiling.
+const TRUE_PEAK_CEILING_DBTP: f32 = -1.0;
 
 pub const CANONICAL_STEMS: [&str; 3] = ["DX", "Music", "Effects"];
 
@@ -23,13 +41,42 @@ pub const CANONICAL_STEMS: [&str; 3] = ["DX", "Music", "Effects"];
 pub struct StemLufsProfile {
     pub integrated: f32,
     pub loudness_range_lu: f32,
+    /// Always measured on the real (non-downmixed) channel layout at the original rate,
+    /// regardless of `max_analysis_samplerate` — inter-sample and cross-channel peaks both
+    /// vanish once the signal is decimated or downmixed to mono.
+    pub true_peak_dbtp: f32,
+    /// Gain (dB) to apply to reach `target_lufs` integrated loudness, clamped so the
+    /// resulting peak never exceeds `TRUE_PEAK_CEILING_DBTP`.
+    pub gain_to_target_db: f32,
     pub momentary: Vec<f32>,
     pub short_term: Vec<f32>,
+    /// `Some` only when the scanner was built `with_feature_extraction(true)`; spectral
+    /// fingerprinting runs a second set of hop-sized FFTs, so it's opt-in rather than
+    /// always-on overhead for callers who only want the loudness curve.
+    pub feature_vector: Option<StemFeatureVector>,
+    /// `Some` only for the DX stem when the scanner was built
+    /// `with_dialogue_activity_gating(true)`.
+    pub dialogue_activity: Option<DialogueActivityProfile>,
+}
+
+/// Where a DX stem's onset detector found speech activity, plus integrated loudness gated
+/// to just those stretches — closer to how dialogue level is actually judged than scoring
+/// silence and music-only passages into the same integrated figure.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DialogueActivityProfile {
+    pub segments: Vec<DialogueActivitySegment>,
+    pub speech_active_integrated: f32,
 }
 
 #[derive(Debug, Clone)]
 pub enum ScanEvent {
-    StemStarted { stem: String },
+    /// `expected_duration_secs` is a best-effort probe of the stem's total length, used by
+    /// the caller to weight this stem's contribution to an overall progress percentage.
+    /// Zero when the container doesn't expose frame-count metadata.
+    StemStarted {
+        stem: String,
+        expected_duration_secs: f32,
+    },
     StemProgress { stem: String, seconds_scanned: f32 },
     StemFinished { stem: String },
 }
@@ -56,7 +103,7 @@ impl std::fmt::Display for ScannerError {
             }
             Self::InvalidStemFormat { stem, path } => write!(
                 f,
-                "Stem file for {stem} is not a valid WAV file: {}",
+                "Stem file for {stem} is not a recognized audio format: {}",
                 path.display()
             ),
             Self::Probe(msg) => write!(f, "Unable to probe stem: {msg}"),
@@ -72,24 +119,85 @@ impl std::error::Error for ScannerError {}
 #[derive(Debug, Clone, Copy)]
 pub struct OfflineLoudnessScanner {
     points_per_second: u32,
+    /// When set below a stem's native sample rate, gated-loudness and loudness-range
+    /// accumulation runs on a downsampled copy of the signal to cut scan time (roughly
+    /// halved going from 48kHz to 24kHz, at <0.1 LU integrated drift). True peak is
+    /// unaffected: it is always measured on the original-rate samples.
+    max_analysis_samplerate: Option<u32>,
+    /// Integrated-loudness target `StemLufsProfile::gain_to_target_db` normalizes toward.
+    target_lufs: f32,
+    /// When set, `scan_stem` also runs a `StemFeatureExtractor` pass and populates
+    /// `StemLufsProfile::feature_vector`.
+    extract_features: bool,
+    /// When set, the DX stem also runs a `DialogueActivityDetector` pass and populates
+    /// `StemLufsProfile::dialogue_activity`.
+    gate_dialogue_activity: bool,
 }
 
 impl Default for OfflineLoudnessScanner {
     fn default() -> Self {
         Self {
             points_per_second: 2,
+            max_analysis_samplerate: None,
+            target_lufs: DEFAULT_TARGET_LUFS,
+            extract_features: false,
+            gate_dialogue_activity: false,
         }
     }
 }
 
 impl OfflineLoudnessScanner {
-    pub fn new(points_per_second: u32) -> Result<Self, ScannerError> {
+    pub fn new(
+        points_per_second: u32,
+        max_analysis_samplerate: Option<u32>,
+    ) -> Result<Self, ScannerError> {
+        Self::with_target_lufs(points_per_second, max_analysis_samplerate, DEFAULT_TARGET_LUFS)
+    }
+
+    /// Same as [`Self::new`] but with an explicit normalization target (e.g. -23 for EBU
+    /// R128 proper rather than the -24 dialnorm default) for delivery specs that call for it.
+    pub fn with_target_lufs(
+        points_per_second: u32,
+        max_analysis_samplerate: Option<u32>,
+        target_lufs: f32,
+    ) -> Result<Self, ScannerError> {
         if !(1..=2).contains(&points_per_second) {
             return Err(ScannerError::InvalidConfig(
                 "points_per_second must be 1 or 2",
             ));
         }
-        Ok(Self { points_per_second })
+        if matches!(max_analysis_samplerate, Some(0)) {
+            return Err(ScannerError::InvalidConfig(
+                "max_analysis_samplerate must be > 0 when set",
+            ));
+        }
+        if !(LUFS_FLOOR..=LUFS_CEILING).contains(&target_lufs) {
+            return Err(ScannerError::InvalidConfig(
+                "target_lufs must be within the -70..=0 LUFS floor/ceiling",
+            ));
+        }
+        Ok(Self {
+            points_per_second,
+            max_analysis_samplerate,
+            target_lufs,
+            extract_features: false,
+            gate_dialogue_activity: false,
+        })
+    }
+
+    /// Opts into per-stem timbral fingerprinting (see [`StemFeatureVector`]) during `scan`.
+    /// Off by default since it runs a second hop-sized FFT pass alongside loudness metering.
+    pub fn with_feature_extraction(mut self, enabled: bool) -> Self {
+        self.extract_features = enabled;
+        self
+    }
+
+    /// Opts into dialogue-activity-gated integrated loudness (see
+    /// [`DialogueActivityProfile`]) for the DX stem during `scan`. Off by default for the
+    /// same reason as `with_feature_extraction`: it's an extra FFT pass most callers don't need.
+    pub fn with_dialogue_activity_gating(mut self, enabled: bool) -> Self {
+        self.gate_dialogue_activity = enabled;
+        self
     }
 
     pub fn resolve_required_stems(
@@ -119,6 +227,16 @@ impl OfflineLoudnessScanner {
     {
         let (event_tx, event_rx) = mpsc::channel::<ScanEvent>();
 
+        // Bounded worker pool: preload N permits, each worker blocks on acquiring one before
+        // decoding and returns it when done, so at most `MAX_CONCURRENT_STEM_SCANS` stems
+        // decode at once regardless of how many stems are requested.
+        let (permit_tx, permit_rx) = mpsc::channel::<()>();
+        let permit_count = MAX_CONCURRENT_STEM_SCANS.min(CANONICAL_STEMS.len()).max(1);
+        for _ in 0..permit_count {
+            let _ = permit_tx.send(());
+        }
+        let permit_rx = Arc::new(Mutex::new(permit_rx));
+
         let mut handles = Vec::with_capacity(CANONICAL_STEMS.len());
         for stem in CANONICAL_STEMS {
             let scanner = *self;
@@ -128,16 +246,26 @@ impl OfflineLoudnessScanner {
                 .cloned()
                 .ok_or(ScannerError::MissingStemPath { stem })?;
             let tx = event_tx.clone();
+            let permit_tx = permit_tx.clone();
+            let permit_rx = Arc::clone(&permit_rx);
 
             let handle =
                 thread::spawn(move || -> Result<(String, StemLufsProfile), ScannerError> {
+                    // Block until a worker slot frees up.
+                    let _ = permit_rx
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .recv();
+
+                    let expected_duration_secs = probe_duration_seconds(&path);
                     let _ = tx.send(ScanEvent::StemStarted {
                         stem: stem_name.clone(),
+                        expected_duration_secs,
                     });
 
                     let stem_for_progress = stem_name.clone();
                     let mut next_progress_secs = STEM_SCAN_PROGRESS_INTERVAL_SECS;
-                    let profile = scanner.scan_stem(&stem_name, &path, |seconds| {
+                    let result = scanner.scan_stem(&stem_name, &path, |seconds| {
                         if seconds >= next_progress_secs {
                             let _ = tx.send(ScanEvent::StemProgress {
                                 stem: stem_for_progress.clone(),
@@ -145,7 +273,11 @@ impl OfflineLoudnessScanner {
                             });
                             next_progress_secs += STEM_SCAN_PROGRESS_INTERVAL_SECS;
                         }
-                    })?;
+                    });
+
+                    // Release the slot before propagating any error so other stems can proceed.
+                    let _ = permit_tx.send(());
+                    let profile = result?;
 
                     let _ = tx.send(ScanEvent::StemFinished {
                         stem: stem_name.clone(),
@@ -195,13 +327,6 @@ impl OfflineLoudnessScanner {
                 path: path.to_path_buf(),
             });
         }
-        if !looks_like_wav(path)? {
-            return Err(ScannerError::InvalidStemFormat {
-                stem: stem_name.to_string(),
-                path: path.to_path_buf(),
-            });
-        }
-
         let file = std::fs::File::open(path).map_err(|e| ScannerError::Decode {
             stem: stem_name.to_string(),
             message: e.to_string(),
@@ -213,14 +338,28 @@ impl OfflineLoudnessScanner {
             hint.with_extension(ext);
         }
 
-        let probed = get_probe()
-            .format(
-                &hint,
-                mss,
-                &FormatOptions::default(),
-                &MetadataOptions::default(),
-            )
-            .map_err(|e| ScannerError::Probe(e.to_string()))?;
+        // Let Symphonia's probe identify the container (WAV, FLAC, AIFF, Ogg, ...) rather
+        // than gating on a fixed RIFF/WAVE header. If nothing in the registry claims the
+        // file, fall back to a quick magic-bytes sniff purely to decide whether this is an
+        // unsupported-but-recognizable format (report the raw probe error) or just not audio
+        // at all (report the clearer `InvalidStemFormat`).
+        let probed = match get_probe().format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        ) {
+            Ok(probed) => probed,
+            Err(e) => {
+                if looks_like_known_container(path)? {
+                    return Err(ScannerError::Probe(e.to_string()));
+                }
+                return Err(ScannerError::InvalidStemFormat {
+                    stem: stem_name.to_string(),
+                    path: path.to_path_buf(),
+                });
+            }
+        };
 
         let mut format = probed.format;
 
@@ -244,8 +383,40 @@ impl OfflineLoudnessScanner {
                 message: e.to_string(),
             })?;
 
-        let mut meter = EbuR128::new(1, sample_rate, Mode::M | Mode::S | Mode::I | Mode::LRA)
+        // Gated-loudness/LRA accumulation runs at the capped rate (if any) on a mono
+        // downmix; true peak needs the real channel layout at the original rate (inter-
+        // sample peaks and cross-channel peaks both vanish once you decimate or downmix),
+        // so it gets its own meter, lazily built once we see the track's channel count.
+        let meter_rate = match self.max_analysis_samplerate {
+            Some(cap) if cap < sample_rate => cap,
+            _ => sample_rate,
+        };
+        let mut meter = EbuR128::new(1, meter_rate, Mode::M | Mode::S | Mode::I | Mode::LRA)
             .map_err(|e| ScannerError::Meter(e.to_string()))?;
+        let mut meter_resampler = if meter_rate != sample_rate {
+            Some(MonoResampler::new(sample_rate, meter_rate).map_err(ScannerError::Meter)?)
+        } else {
+            None
+        };
+        let mut true_peak_meter: Option<EbuR128> = None;
+        let mut true_peak_channels: u32 = 0;
+
+        // Runs on the original-rate mono downmix (independent of `max_analysis_samplerate`,
+        // same reasoning as true peak: a fingerprint should reflect the real signal).
+        let mut feature_extractor = self
+            .extract_features
+            .then(|| StemFeatureExtractor::new(sample_rate));
+        let mut feature_hop_buffer: Vec<f32> = Vec::new();
+
+        let mut activity_detector = (self.gate_dialogue_activity && stem_name == DIALOGUE_ACTIVITY_STEM)
+            .then(|| DialogueActivityDetector::new(sample_rate));
+        let mut activity_hop_buffer: Vec<f32> = Vec::new();
+        let mut activity_gated_meter = match activity_detector.as_ref() {
+            Some(_) => Some(
+                EbuR128::new(1, sample_rate, Mode::I).map_err(|e| ScannerError::Meter(e.to_string()))?,
+            ),
+            None => None,
+        };
 
         let capture_step_samples = sample_rate as f64 / self.points_per_second as f64;
         let mut next_capture_sample = 0.0_f64;
@@ -295,14 +466,64 @@ impl OfflineLoudnessScanner {
                 }
             };
 
-            let mono = decode_to_normalized_mono(decoded);
+            let (mono, interleaved, channels) = decode_to_mono_and_interleaved(decoded);
             if mono.is_empty() {
                 continue;
             }
 
-            meter
-                .add_frames_f32(&mono)
-                .map_err(|e| ScannerError::Meter(e.to_string()))?;
+            if true_peak_meter.is_none() {
+                true_peak_channels = channels.max(1) as u32;
+                true_peak_meter = Some(
+                    EbuR128::new(true_peak_channels, sample_rate, Mode::TRUE_PEAK)
+                        .map_err(|e| ScannerError::Meter(e.to_string()))?,
+                );
+            }
+            if let Some(tp_meter) = true_peak_meter.as_mut() {
+                tp_meter
+                    .add_frames_f32(&interleaved)
+                    .map_err(|e| ScannerError::Meter(e.to_string()))?;
+            }
+
+            if let Some(extractor) = feature_extractor.as_mut() {
+                feature_hop_buffer.extend_from_slice(&mono);
+                let hop_size = extractor.hop_size();
+                while feature_hop_buffer.len() >= hop_size {
+                    let hop: Vec<f32> = feature_hop_buffer.drain(..hop_size).collect();
+                    extractor.process_hop(&hop);
+                }
+            }
+
+            if let Some(detector) = activity_detector.as_mut() {
+                activity_hop_buffer.extend_from_slice(&mono);
+                let hop_size = detector.hop_size();
+                while activity_hop_buffer.len() >= hop_size {
+                    let hop: Vec<f32> = activity_hop_buffer.drain(..hop_size).collect();
+                    let is_active = detector.process_hop(&hop);
+                    if is_active {
+                        if let Some(gated_meter) = activity_gated_meter.as_mut() {
+                            gated_meter
+                                .add_frames_f32(&hop)
+                                .map_err(|e| ScannerError::Meter(e.to_string()))?;
+                        }
+                    }
+                }
+            }
+
+            match meter_resampler.as_mut() {
+                Some(resampler) => {
+                    let downsampled = resampler.process(&mono);
+                    if !downsampled.is_empty() {
+                        meter
+                            .add_frames_f32(&downsampled)
+                            .map_err(|e| ScannerError::Meter(e.to_string()))?;
+                    }
+                }
+                None => {
+                    meter
+                        .add_frames_f32(&mono)
+                        .map_err(|e| ScannerError::Meter(e.to_string()))?;
+                }
+            }
             processed_samples += mono.len() as u64;
 
             while (processed_samples as f64) >= next_capture_sample {
@@ -314,37 +535,146 @@ impl OfflineLoudnessScanner {
             on_progress(processed_samples as f32 / sample_rate as f32);
         }
 
+        let true_peak_dbtp = read_true_peak_dbtp(true_peak_meter.as_ref(), true_peak_channels);
+        let integrated = read_lufs(meter.loudness_global());
+        let gain_to_target_db = gain_to_target_db(integrated, true_peak_dbtp, self.target_lufs);
+
+        let feature_vector = feature_extractor.as_mut().map(|extractor| {
+            // Zero-pad and process whatever's left under a full hop rather than drop it;
+            // discarding the trailing fraction of a second would skew short stems the most.
+            if !feature_hop_buffer.is_empty() {
+                feature_hop_buffer.resize(extractor.hop_size(), 0.0);
+                extractor.process_hop(&feature_hop_buffer);
+            }
+            extractor.finish()
+        });
+
+        let dialogue_activity = match (activity_detector.as_mut(), activity_gated_meter.as_ref()) {
+            (Some(detector), Some(gated_meter)) => {
+                // Leftover partial hop is dropped rather than zero-padded: padding it with
+                // silence would only ever pull the adaptive threshold down right at the tail,
+                // never add a genuine onset, so there's nothing worth recovering here.
+                let segments = detector.finish();
+                Some(DialogueActivityProfile {
+                    segments,
+                    speech_active_integrated: read_lufs(gated_meter.loudness_global()),
+                })
+            }
+            _ => None,
+        };
+
         Ok(StemLufsProfile {
-            integrated: read_lufs(meter.loudness_global()),
+            integrated,
             loudness_range_lu: read_lu(meter.loudness_range()),
+            true_peak_dbtp,
+            gain_to_target_db,
             momentary,
             short_term,
+            feature_vector,
+            dialogue_activity,
         })
     }
 }
 
-fn decode_to_normalized_mono(decoded: AudioBufferRef<'_>) -> Vec<f32> {
+/// Best-effort probe of a stem's total duration without decoding any audio — used only to
+/// weight this stem's share of the overall progress percentage. Returns `0.0` when the
+/// container doesn't expose frame-count metadata (the caller then weights this stem as if
+/// it were one second long).
+fn probe_duration_seconds(path: &Path) -> f32 {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return 0.0,
+    };
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = match get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    ) {
+        Ok(probed) => probed,
+        Err(_) => return 0.0,
+    };
+
+    let Some(track) = probed.format.default_track() else {
+        return 0.0;
+    };
+    let (Some(n_frames), Some(sample_rate)) =
+        (track.codec_params.n_frames, track.codec_params.sample_rate)
+    else {
+        return 0.0;
+    };
+    if sample_rate == 0 {
+        return 0.0;
+    }
+
+    n_frames as f32 / sample_rate as f32
+}
+
+/// Decodes one packet into both a clamped mono downmix (for gated-loudness accumulation,
+/// which has always run on mono) and the raw, unclamped interleaved channels (for true-peak
+/// metering, which needs the real layout and must never be clipped before peak detection).
+fn decode_to_mono_and_interleaved(decoded: AudioBufferRef<'_>) -> (Vec<f32>, Vec<f32>, usize) {
     let spec = *decoded.spec();
     let channels = spec.channels.count();
     if channels == 0 {
-        return Vec::new();
+        return (Vec::new(), Vec::new(), 0);
     }
 
     let mut sample_buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
     sample_buffer.copy_interleaved_ref(decoded);
     let interleaved = sample_buffer.samples();
 
-    interleaved
+    let mono = interleaved
         .chunks_exact(channels)
         .map(|frame| {
             let sum: f32 = frame.iter().copied().sum();
             let mono = sum / channels as f32;
             mono.clamp(-1.0, 1.0)
         })
-        .collect()
+        .collect();
+
+    (mono, interleaved.to_vec(), channels)
+}
+
+/// Reads the max true peak across all channels from the meter (linear amplitude, per the
+/// `ebur128` crate) and converts it to dBTP. `None`/zero channels (an empty stem) reports
+/// digital silence rather than a spurious 0 dBTP.
+fn read_true_peak_dbtp(meter: Option<&EbuR128>, channels: u32) -> f32 {
+    let Some(meter) = meter else {
+        return TRUE_PEAK_SILENCE_DBTP;
+    };
+
+    let max_linear = (0..channels)
+        .filter_map(|ch| meter.true_peak(ch).ok())
+        .fold(0.0_f64, f64::max);
+
+    if max_linear <= TRUE_PEAK_EPSILON {
+        TRUE_PEAK_SILENCE_DBTP
+    } else {
+        (20.0 * max_linear.log10()) as f32
+    }
 }
 
-fn looks_like_wav(path: &Path) -> Result<bool, ScannerError> {
+/// Gain (dB) to reach `target_lufs` integrated loudness, clamped so the resulting peak
+/// never exceeds `TRUE_PEAK_CEILING_DBTP`.
+fn gain_to_target_db(integrated_lufs: f32, true_peak_dbtp: f32, target_lufs: f32) -> f32 {
+    let gain_for_target = target_lufs - integrated_lufs;
+    let max_gain_before_clip = TRUE_PEAK_CEILING_DBTP - true_peak_dbtp;
+    gain_for_target.min(max_gain_before_clip)
+}
+
+/// Quick magic-bytes sniff over the handful of containers Symphonia's probe recognizes
+/// (RIFF/WAVE, AIFF/AIFC, FLAC, Ogg, ID3-tagged or bare MPEG audio). This is *not* a gate —
+/// the probe above always gets first try at every file — it only distinguishes "this looks
+/// like a known format that failed to decode for some other reason" from "this isn't audio
+/// at all" when the probe comes back empty, so `InvalidStemFormat` stays informative.
+fn looks_like_known_container(path: &Path) -> Result<bool, ScannerError> {
     let mut file = std::fs::File::open(path).map_err(|e| ScannerError::Decode {
         stem: path.display().to_string(),
         message: e.to_string(),
@@ -355,10 +685,21 @@ fn looks_like_wav(path: &Path) -> Result<bool, ScannerError> {
             stem: path.display().to_string(),
             message: e.to_string(),
         })?;
-    if bytes_read < header.len() {
+    if bytes_read < 4 {
         return Ok(false);
     }
-    Ok(&header[0..4] == b"RIFF" && &header[8..12] == b"WAVE")
+
+    let is_riff_wave = bytes_read >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE";
+    let is_aiff = bytes_read >= 12
+        && &header[0..4] == b"FORM"
+        && (&header[8..12] == b"AIFF" || &header[8..12] == b"AIFC");
+    let is_flac = &header[0..4] == b"fLaC";
+    let is_ogg = &header[0..4] == b"OggS";
+    let is_id3 = &header[0..3] == b"ID3";
+    // Bare MPEG audio frame sync: 11 set high bits (0xFFE.. through 0xFFF..).
+    let is_mpeg_sync = header[0] == 0xFF && (header[1] & 0xE0) == 0xE0;
+
+    Ok(is_riff_wave || is_aiff || is_flac || is_ogg || is_id3 || is_mpeg_sync)
 }
 
 fn read_lufs(value: Result<f64, ebur128::Error>) -> f32 {
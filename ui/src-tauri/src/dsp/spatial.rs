@@ -29,8 +29,17 @@ impl SpatialAnalyzer {
             return SpatialMetrics::default();
         }
 
-        let dialogue = &frame.dialogue_raw[..len];
-        let background = &frame.background_raw[..len];
+        // `len` is a per-channel frame count (see
+        // [`AudioFrame::len`](crate::dsp::AudioFrame::len)); compare the full interleaved
+        // span rather than downmixing each stem to mono first, so a stereo (or wider) pair
+        // of stems is correlated channel-by-channel — e.g. for stereo, dialogue-L lines up
+        // against background-L and dialogue-R against background-R — instead of collapsing
+        // away the exact per-channel phase/width information the spatial module exists to
+        // expose.
+        let channels = frame.channels.max(1);
+        let sample_count = len * channels;
+        let dialogue = &frame.dialogue_raw[..sample_count.min(frame.dialogue_raw.len())];
+        let background = &frame.background_raw[..sample_count.min(frame.background_raw.len())];
 
         SpatialMetrics {
             phase_correlation: pearson_correlation(dialogue, background),
@@ -97,4 +106,46 @@ mod tests {
         assert!((points[0].x - (2.0 * SQRT_HALF)).abs() < 1.0e-6);
         assert!(points[0].y.abs() < 1.0e-6);
     }
+
+    #[test]
+    fn process_frame_reads_the_whole_stereo_frame_not_just_the_first_channel_interleaved() {
+        let analyzer = SpatialAnalyzer::new();
+        // Stereo, matching dialogue/background so correlation should still read as 1.0
+        // once the buffers are correctly read in full rather than truncated mid-frame.
+        let dialogue_raw = vec![0.1, 0.1, 0.2, 0.2, 0.3, 0.3, 0.4, 0.4];
+        let background_raw = dialogue_raw.clone();
+        let frame = SyncedAudioFrame {
+            channels: 2,
+            dialogue_raw,
+            background_raw,
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        // One Lissajous point per interleaved sample (4 frames * 2 channels), not one per
+        // frame — the channels are never averaged together.
+        assert_eq!(metrics.lissajous_points.len(), 8);
+        assert!((metrics.phase_correlation - 1.0).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn process_frame_reflects_per_channel_divergence_not_just_the_averaged_mix() {
+        let analyzer = SpatialAnalyzer::new();
+        // Left channel is identical between dialogue and background; right channel is
+        // perfectly out of phase. Averaging the two channels into mono before correlating
+        // would hide the identical left channel entirely (the mono mixes here correlate at
+        // -1.0); comparing true per-channel data instead reads about -0.73.
+        let dialogue_raw = vec![0.1, 0.2, 0.2, 0.4, 0.3, 0.6, 0.4, 0.8];
+        let background_raw = vec![0.1, -0.2, 0.2, -0.4, 0.3, -0.6, 0.4, -0.8];
+        let frame = SyncedAudioFrame {
+            channels: 2,
+            dialogue_raw,
+            background_raw,
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        assert!(metrics.phase_correlation < 0.5);
+        assert!(metrics.phase_correlation > -0.9);
+    }
 }
@@ -1,48 +1,94 @@
 use std::sync::Arc;
 
-use rustfft::num_complex::Complex32;
-use rustfft::{Fft, FftPlanner};
+use realfft::num_complex::Complex32;
+use realfft::{RealFftPlanner, RealToComplex};
 
 use crate::dsp::SyncedAudioFrame;
 
 const EPSILON: f32 = 1.0e-12;
-const SPEECH_LOW_HZ: f32 = 1_000.0;
-const SPEECH_HIGH_HZ: f32 = 4_000.0;
-
-#[derive(Debug, Clone, Copy, Default)]
+pub(crate) const SPEECH_LOW_HZ: f32 = 1_000.0;
+pub(crate) const SPEECH_HIGH_HZ: f32 = 4_000.0;
+const ROLLOFF_ENERGY_FRACTION: f32 = 0.85;
+/// Upper edge of the "low" band in [`band_energy_ratios`]: rumble/bass content below this
+/// rarely carries timbral identity on its own, so it's bucketed together.
+const LOW_BAND_HZ: f32 = 250.0;
+/// Upper edge of the "mid" band in [`band_energy_ratios`] (and lower edge of "high"):
+/// roughly where most speech/instrument fundamentals give way to air and sibilance.
+const HIGH_BAND_HZ: f32 = 4_000.0;
+/// Hop size `StemFeatureExtractor` windows and FFTs at — independent of whatever packet
+/// size the decoder happens to hand back, same rationale as `SpectralAnalyzer`'s frame size.
+const FEATURE_HOP_SIZE: usize = 2_048;
+/// Number of Bark critical bands covered up to Nyquist (human hearing tops out at ~24).
+const NUM_BARK_BANDS: usize = 24;
+/// Masking offset subtracted from the spread background threshold (~5.5 dB for
+/// noise-like maskers; tonal maskers would need a larger offset, but background stems
+/// are broadband enough that this is a reasonable fixed value).
+const MASKING_OFFSET_DB: f32 = 5.5;
+/// Spreading-function slope applied below the masker band (steeper — a masker's
+/// influence falls off quickly toward lower frequencies).
+const SPREAD_LOWER_SLOPE_DB_PER_BARK: f32 = 25.0;
+/// Spreading-function slope applied above the masker band (gentler — a masker's
+/// influence reaches further toward higher frequencies).
+const SPREAD_UPPER_SLOPE_DB_PER_BARK: f32 = -10.0;
+
+#[derive(Debug, Clone, Default)]
 pub struct SpectralMetrics {
     pub dialogue_centroid_hz: f32,
     pub background_centroid_hz: f32,
+    /// True when most 1-4 kHz Bark bands are masked; kept for callers that just want a
+    /// single "is dialogue in trouble" flag. See `masked_bark_bands` for the per-band view.
     pub speech_pocket_masked: bool,
+    /// Per-Bark-band mask flags (`true` = dialogue energy in that band falls below the
+    /// spread background masking threshold), lowest band first.
+    pub masked_bark_bands: Vec<bool>,
+    /// Fraction (0.0-1.0) of Bark bands inside the 1-4 kHz speech region that are masked.
+    pub speech_pocket_masked_fraction: f32,
     pub dialogue_speech_energy: f32,
     pub background_speech_energy: f32,
     pub snr_db: f32,
+    /// Root-mean-square of the positive frame-to-frame magnitude differences; an
+    /// onset/transient indicator (high on attacks, near-zero on steady tone/noise).
+    pub dialogue_flux: f32,
+    pub background_flux: f32,
+    /// Frequency in Hz below which 85% of the frame's spectral energy lies.
+    pub dialogue_rolloff_hz: f32,
+    pub background_rolloff_hz: f32,
+    /// Geometric mean of the magnitude spectrum over its arithmetic mean. ~1.0 for
+    /// noise-like content, ~0 for tonal content.
+    pub dialogue_flatness: f32,
+    pub background_flatness: f32,
 }
 
 pub struct SpectralAnalyzer {
     sample_rate: u32,
     frame_size: usize,
-    fft: Arc<dyn Fft<f32>>,
+    r2c: Arc<dyn RealToComplex<f32>>,
     window: Vec<f32>,
-    dialogue_buffer: Vec<Complex32>,
-    background_buffer: Vec<Complex32>,
+    dialogue_input: Vec<f32>,
+    background_input: Vec<f32>,
+    dialogue_spectrum: Vec<Complex32>,
+    background_spectrum: Vec<Complex32>,
+    dialogue_prev_magnitudes: Option<Vec<f32>>,
+    background_prev_magnitudes: Option<Vec<f32>>,
 }
 
 impl SpectralAnalyzer {
     pub fn new(sample_rate: u32, frame_size: usize) -> Self {
-        let mut planner = FftPlanner::<f32>::new();
-        let fft = planner.plan_fft_forward(frame_size);
+        let mut planner = RealFftPlanner::<f32>::new();
+        let r2c = planner.plan_fft_forward(frame_size.max(1));
         let window = hann_window(frame_size);
-        let dialogue_buffer = vec![Complex32::new(0.0, 0.0); frame_size];
-        let background_buffer = vec![Complex32::new(0.0, 0.0); frame_size];
 
         Self {
             sample_rate,
             frame_size,
-            fft,
+            dialogue_input: r2c.make_input_vec(),
+            background_input: r2c.make_input_vec(),
+            dialogue_spectrum: r2c.make_output_vec(),
+            background_spectrum: r2c.make_output_vec(),
+            r2c,
             window,
-            dialogue_buffer,
-            background_buffer,
+            dialogue_prev_magnitudes: None,
+            background_prev_magnitudes: None,
         }
     }
 
@@ -51,18 +97,24 @@ impl SpectralAnalyzer {
             return SpectralMetrics::default();
         }
 
-        fill_fft_buffer(&mut self.dialogue_buffer, &frame.dialogue_raw, &self.window);
-        fill_fft_buffer(
-            &mut self.background_buffer,
+        fill_window_buffer(&mut self.dialogue_input, &frame.dialogue_raw, &self.window);
+        fill_window_buffer(
+            &mut self.background_input,
             &frame.background_raw,
             &self.window,
         );
 
-        self.fft.process(&mut self.dialogue_buffer);
-        self.fft.process(&mut self.background_buffer);
+        // `process` overwrites its input buffer as scratch space, which is fine since we
+        // rebuild it from the raw frame every call.
+        let _ = self
+            .r2c
+            .process(&mut self.dialogue_input, &mut self.dialogue_spectrum);
+        let _ = self
+            .r2c
+            .process(&mut self.background_input, &mut self.background_spectrum);
 
-        let dialogue_magnitudes = magnitudes(&self.dialogue_buffer);
-        let background_magnitudes = magnitudes(&self.background_buffer);
+        let dialogue_magnitudes = magnitudes(&self.dialogue_spectrum);
+        let background_magnitudes = magnitudes(&self.background_spectrum);
 
         let dialogue_centroid_hz = spectral_centroid_hz(&dialogue_magnitudes, self.sample_rate);
         let background_centroid_hz = spectral_centroid_hz(&background_magnitudes, self.sample_rate);
@@ -80,18 +132,46 @@ impl SpectralAnalyzer {
         );
         let snr_db = signal_to_noise_ratio_db(&frame.dialogue_raw, &frame.background_raw);
 
+        let dialogue_flux = spectral_flux(&dialogue_magnitudes, self.dialogue_prev_magnitudes.as_deref());
+        let background_flux = spectral_flux(
+            &background_magnitudes,
+            self.background_prev_magnitudes.as_deref(),
+        );
+        let dialogue_rolloff_hz = spectral_rolloff_hz(&dialogue_magnitudes, self.sample_rate);
+        let background_rolloff_hz = spectral_rolloff_hz(&background_magnitudes, self.sample_rate);
+        let dialogue_flatness = spectral_flatness(&dialogue_magnitudes);
+        let background_flatness = spectral_flatness(&background_magnitudes);
+
+        let (masked_bark_bands, speech_pocket_masked_fraction) = bark_masking(
+            &dialogue_magnitudes,
+            &background_magnitudes,
+            self.sample_rate,
+        );
+        let speech_pocket_masked = speech_pocket_masked_fraction > 0.5;
+
+        self.dialogue_prev_magnitudes = Some(dialogue_magnitudes);
+        self.background_prev_magnitudes = Some(background_magnitudes);
+
         SpectralMetrics {
             dialogue_centroid_hz,
             background_centroid_hz,
-            speech_pocket_masked: background_speech_energy > dialogue_speech_energy,
+            speech_pocket_masked,
+            masked_bark_bands,
+            speech_pocket_masked_fraction,
             dialogue_speech_energy,
             background_speech_energy,
             snr_db,
+            dialogue_flux,
+            background_flux,
+            dialogue_rolloff_hz,
+            background_rolloff_hz,
+            dialogue_flatness,
+            background_flatness,
         }
     }
 }
 
-fn hann_window(frame_size: usize) -> Vec<f32> {
+pub(crate) fn hann_window(frame_size: usize) -> Vec<f32> {
     if frame_size <= 1 {
         return vec![1.0; frame_size];
     }
@@ -102,21 +182,15 @@ fn hann_window(frame_size: usize) -> Vec<f32> {
         .collect()
 }
 
-fn fill_fft_buffer(buffer: &mut [Complex32], input: &[f32], window: &[f32]) {
+pub(crate) fn fill_window_buffer(buffer: &mut [f32], input: &[f32], window: &[f32]) {
     for (i, sample) in buffer.iter_mut().enumerate() {
         let v = input.get(i).copied().unwrap_or(0.0);
-        sample.re = v * window[i];
-        sample.im = 0.0;
+        *sample = v * window[i];
     }
 }
 
 fn magnitudes(spectrum: &[Complex32]) -> Vec<f32> {
-    let nyquist_bins = spectrum.len() / 2 + 1;
-    spectrum
-        .iter()
-        .take(nyquist_bins)
-        .map(|c| c.norm())
-        .collect()
+    spectrum.iter().map(|c| c.norm()).collect()
 }
 
 fn spectral_centroid_hz(magnitudes: &[f32], sample_rate: u32) -> f32 {
@@ -173,6 +247,163 @@ fn speech_band_energy(magnitudes: &[f32], sample_rate: u32, low_hz: f32, high_hz
         .sum::<f32>()
 }
 
+/// Root-mean-square of the positive frame-to-frame magnitude differences, averaged over
+/// the bins so the bin count itself doesn't inflate the value. Zero on the first frame
+/// (no history yet).
+fn spectral_flux(magnitudes: &[f32], prev_magnitudes: Option<&[f32]>) -> f32 {
+    let Some(prev) = prev_magnitudes else {
+        return 0.0;
+    };
+    if magnitudes.is_empty() || prev.len() != magnitudes.len() {
+        return 0.0;
+    }
+
+    let sum_sq: f32 = magnitudes
+        .iter()
+        .zip(prev.iter())
+        .map(|(&curr, &prior)| (curr - prior).max(0.0).powi(2))
+        .sum();
+
+    (sum_sq / magnitudes.len() as f32).sqrt()
+}
+
+/// Frequency below which `ROLLOFF_ENERGY_FRACTION` of the cumulative spectral energy lies.
+fn spectral_rolloff_hz(magnitudes: &[f32], sample_rate: u32) -> f32 {
+    if magnitudes.is_empty() {
+        return 0.0;
+    }
+
+    let fft_size = (magnitudes.len().saturating_sub(1) * 2).max(1);
+    let hz_per_bin = sample_rate as f32 / fft_size as f32;
+
+    let total_energy: f32 = magnitudes.iter().map(|m| m * m).sum();
+    if total_energy <= EPSILON {
+        return 0.0;
+    }
+
+    let threshold = total_energy * ROLLOFF_ENERGY_FRACTION;
+    let mut cumulative = 0.0_f32;
+    for (bin, &amp) in magnitudes.iter().enumerate() {
+        cumulative += amp * amp;
+        if cumulative >= threshold {
+            return bin as f32 * hz_per_bin;
+        }
+    }
+
+    (magnitudes.len() - 1) as f32 * hz_per_bin
+}
+
+/// Geometric mean of the magnitude spectrum over its arithmetic mean, computed via the
+/// log domain to avoid underflow on long spectra. ~1.0 for noise, ~0 for tonal content.
+fn spectral_flatness(magnitudes: &[f32]) -> f32 {
+    if magnitudes.is_empty() {
+        return 0.0;
+    }
+
+    let arithmetic_mean = magnitudes.iter().copied().sum::<f32>() / magnitudes.len() as f32;
+    if arithmetic_mean <= EPSILON {
+        return 0.0;
+    }
+
+    let log_sum: f32 = magnitudes.iter().map(|&m| (m + EPSILON).ln()).sum();
+    let geometric_mean = (log_sum / magnitudes.len() as f32).exp();
+
+    (geometric_mean / arithmetic_mean).clamp(0.0, 1.0)
+}
+
+/// Maps a frequency in Hz to its Bark-scale critical-band number via Zwicker & Terhardt's
+/// formula: `z(f) = 13*atan(0.00076*f) + 3.5*atan((f/7500)^2)`.
+fn bark_of_hz(hz: f32) -> f32 {
+    13.0 * (0.00076 * hz).atan() + 3.5 * (hz / 7_500.0).powi(2).atan()
+}
+
+/// Sums squared magnitudes per Bark band (lowest band first, `NUM_BARK_BANDS` total).
+fn bark_band_energy(magnitudes: &[f32], sample_rate: u32) -> Vec<f32> {
+    let mut bands = vec![0.0_f32; NUM_BARK_BANDS];
+    if magnitudes.is_empty() {
+        return bands;
+    }
+
+    let fft_size = (magnitudes.len().saturating_sub(1) * 2).max(1);
+    let hz_per_bin = sample_rate as f32 / fft_size as f32;
+
+    for (bin, &amp) in magnitudes.iter().enumerate() {
+        let hz = bin as f32 * hz_per_bin;
+        let band = (bark_of_hz(hz) as usize).min(NUM_BARK_BANDS - 1);
+        bands[band] += amp * amp;
+    }
+
+    bands
+}
+
+/// Triangular spreading function in the Bark domain: a masker in band `j` contributes
+/// `spread_db(i - j)` dB to band `i`, peaking at 0 dB when `i == j` and falling off at
+/// `SPREAD_LOWER_SLOPE_DB_PER_BARK` below the masker and `SPREAD_UPPER_SLOPE_DB_PER_BARK`
+/// above it (bands are ~1 Bark wide, so `i - j` approximates the Bark distance).
+fn spread_db(delta_bands: f32) -> f32 {
+    if delta_bands <= 0.0 {
+        SPREAD_LOWER_SLOPE_DB_PER_BARK * delta_bands
+    } else {
+        SPREAD_UPPER_SLOPE_DB_PER_BARK * delta_bands
+    }
+}
+
+fn to_db(energy: f32) -> f32 {
+    10.0 * (energy + EPSILON).log10()
+}
+
+/// Computes the per-Bark-band background masking threshold, spreads it across
+/// neighbouring bands, and reports which dialogue bands fall below it (masked) plus
+/// what fraction of the 1-4 kHz speech region that covers.
+fn bark_masking(
+    dialogue_magnitudes: &[f32],
+    background_magnitudes: &[f32],
+    sample_rate: u32,
+) -> (Vec<bool>, f32) {
+    let dialogue_bands_db: Vec<f32> = bark_band_energy(dialogue_magnitudes, sample_rate)
+        .into_iter()
+        .map(to_db)
+        .collect();
+    let background_bands_db: Vec<f32> = bark_band_energy(background_magnitudes, sample_rate)
+        .into_iter()
+        .map(to_db)
+        .collect();
+
+    let masked_bark_bands: Vec<bool> = (0..NUM_BARK_BANDS)
+        .map(|i| {
+            let threshold_db = (0..NUM_BARK_BANDS)
+                .map(|j| background_bands_db[j] + spread_db(i as f32 - j as f32))
+                .fold(f32::NEG_INFINITY, f32::max)
+                - MASKING_OFFSET_DB;
+            dialogue_bands_db[i] < threshold_db
+        })
+        .collect();
+
+    let fft_size = (dialogue_magnitudes.len().saturating_sub(1) * 2).max(1);
+    let hz_per_bin = sample_rate as f32 / fft_size as f32;
+    let mut in_speech_region = [false; NUM_BARK_BANDS];
+    for bin in 0..dialogue_magnitudes.len() {
+        let hz = bin as f32 * hz_per_bin;
+        if hz < SPEECH_LOW_HZ || hz > SPEECH_HIGH_HZ {
+            continue;
+        }
+        let band = (bark_of_hz(hz) as usize).min(NUM_BARK_BANDS - 1);
+        in_speech_region[band] = true;
+    }
+
+    let speech_band_count = in_speech_region.iter().filter(|&&b| b).count();
+    let speech_pocket_masked_fraction = if speech_band_count == 0 {
+        0.0
+    } else {
+        let masked_count = (0..NUM_BARK_BANDS)
+            .filter(|&band| in_speech_region[band] && masked_bark_bands[band])
+            .count();
+        masked_count as f32 / speech_band_count as f32
+    };
+
+    (masked_bark_bands, speech_pocket_masked_fraction)
+}
+
 fn signal_to_noise_ratio_db(dialogue: &[f32], background: &[f32]) -> f32 {
     let len = dialogue.len().min(background.len());
     if len == 0 {
@@ -190,6 +421,261 @@ fn signal_to_noise_ratio_db(dialogue: &[f32], background: &[f32]) -> f32 {
     (10.0 * ratio.log10()).clamp(-20.0, 60.0)
 }
 
+/// Fixed-size timbral fingerprint for a single stem, aggregated across the whole file so
+/// callers can cluster or compare stems (e.g. flag an "Effects" stem that's actually
+/// dialogue-heavy) without holding onto the full per-hop descriptor stream.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct StemFeatureVector {
+    pub centroid_hz_mean: f32,
+    pub centroid_hz_variance: f32,
+    pub rolloff_hz_mean: f32,
+    pub rolloff_hz_variance: f32,
+    pub flatness_mean: f32,
+    pub flatness_variance: f32,
+    pub flux_mean: f32,
+    pub flux_variance: f32,
+    pub zero_crossing_rate_mean: f32,
+    pub zero_crossing_rate_variance: f32,
+    /// Fraction (0.0-1.0) of spectral energy below [`LOW_BAND_HZ`], averaged over the scan.
+    pub low_band_energy_ratio_mean: f32,
+    pub low_band_energy_ratio_variance: f32,
+    /// Fraction of spectral energy between [`LOW_BAND_HZ`] and [`HIGH_BAND_HZ`].
+    pub mid_band_energy_ratio_mean: f32,
+    pub mid_band_energy_ratio_variance: f32,
+    /// Fraction of spectral energy above [`HIGH_BAND_HZ`].
+    pub high_band_energy_ratio_mean: f32,
+    pub high_band_energy_ratio_variance: f32,
+}
+
+/// Alias for [`StemFeatureVector`] used where callers compare two descriptors rather than
+/// just report one — same fixed-length shape, emphasizing the clustering/matching use case
+/// (see [`fingerprint_distance`]) over `OfflineLoudnessScanner`'s per-scan QC reporting.
+pub type StemFingerprint = StemFeatureVector;
+
+/// Per-feature normalization divisors bringing [`StemFingerprint`]'s heterogeneous units
+/// (Hz, unitless ratios, fractions) onto a roughly comparable scale before
+/// [`fingerprint_distance`] sums their squared differences. Approximate by design — good
+/// enough for nearest-neighbor clustering and duplicate-cue detection, not calibrated to any
+/// perceptual distance metric.
+const CENTROID_NORM_HZ: f32 = 8_000.0;
+const ROLLOFF_NORM_HZ: f32 = 12_000.0;
+
+/// Euclidean distance between two fingerprints after normalizing each feature (mean and
+/// variance) by [`CENTROID_NORM_HZ`]/[`ROLLOFF_NORM_HZ`] for the Hz-valued fields and
+/// leaving the already-unitless ratio/fraction fields (flatness, flux, zero-crossing rate,
+/// band-energy ratios) as-is. Smaller is more similar; two fingerprints of the same
+/// recurring musical cue should land much closer together than unrelated material.
+pub fn fingerprint_distance(a: &StemFingerprint, b: &StemFingerprint) -> f32 {
+    let centroid_norm_sq = CENTROID_NORM_HZ * CENTROID_NORM_HZ;
+    let rolloff_norm_sq = ROLLOFF_NORM_HZ * ROLLOFF_NORM_HZ;
+
+    let terms = [
+        (a.centroid_hz_mean - b.centroid_hz_mean) / CENTROID_NORM_HZ,
+        (a.centroid_hz_variance - b.centroid_hz_variance) / centroid_norm_sq,
+        (a.rolloff_hz_mean - b.rolloff_hz_mean) / ROLLOFF_NORM_HZ,
+        (a.rolloff_hz_variance - b.rolloff_hz_variance) / rolloff_norm_sq,
+        a.flatness_mean - b.flatness_mean,
+        a.flatness_variance - b.flatness_variance,
+        a.flux_mean - b.flux_mean,
+        a.flux_variance - b.flux_variance,
+        a.zero_crossing_rate_mean - b.zero_crossing_rate_mean,
+        a.zero_crossing_rate_variance - b.zero_crossing_rate_variance,
+        a.low_band_energy_ratio_mean - b.low_band_energy_ratio_mean,
+        a.low_band_energy_ratio_variance - b.low_band_energy_ratio_variance,
+        a.mid_band_energy_ratio_mean - b.mid_band_energy_ratio_mean,
+        a.mid_band_energy_ratio_variance - b.mid_band_energy_ratio_variance,
+        a.high_band_energy_ratio_mean - b.high_band_energy_ratio_mean,
+        a.high_band_energy_ratio_variance - b.high_band_energy_ratio_variance,
+    ];
+
+    terms.iter().map(|t| t * t).sum::<f32>().sqrt()
+}
+
+/// Online mean/variance accumulator (Welford's algorithm) so aggregating a stem's worth of
+/// per-hop descriptors doesn't require buffering them all in memory first.
+#[derive(Debug, Clone, Copy, Default)]
+struct RunningStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn update(&mut self, value: f32) {
+        self.count += 1;
+        let delta = value as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = value as f64 - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    fn mean(&self) -> f32 {
+        self.mean as f32
+    }
+
+    /// Population variance; zero until at least two samples have been seen.
+    fn variance(&self) -> f32 {
+        if self.count < 2 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64) as f32
+        }
+    }
+}
+
+/// Per-hop timbral feature extractor for a single mono signal, aggregating spectral
+/// centroid, rolloff, flatness, flux, and zero-crossing rate into running mean/variance.
+/// Shares the windowing/FFT machinery `SpectralAnalyzer` uses for the dialogue/background
+/// pair, just over one channel at a time — built for `OfflineLoudnessScanner` to fingerprint
+/// a stem hop-by-hop as it decodes, rather than requiring a second decode pass.
+pub struct StemFeatureExtractor {
+    sample_rate: u32,
+    r2c: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    prev_magnitudes: Option<Vec<f32>>,
+    centroid_stats: RunningStats,
+    rolloff_stats: RunningStats,
+    flatness_stats: RunningStats,
+    flux_stats: RunningStats,
+    zero_crossing_stats: RunningStats,
+    low_band_stats: RunningStats,
+    mid_band_stats: RunningStats,
+    high_band_stats: RunningStats,
+}
+
+impl StemFeatureExtractor {
+    pub fn new(sample_rate: u32) -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let r2c = planner.plan_fft_forward(FEATURE_HOP_SIZE);
+        let window = hann_window(FEATURE_HOP_SIZE);
+
+        Self {
+            sample_rate,
+            input: r2c.make_input_vec(),
+            spectrum: r2c.make_output_vec(),
+            r2c,
+            window,
+            prev_magnitudes: None,
+            centroid_stats: RunningStats::default(),
+            rolloff_stats: RunningStats::default(),
+            flatness_stats: RunningStats::default(),
+            flux_stats: RunningStats::default(),
+            zero_crossing_stats: RunningStats::default(),
+            low_band_stats: RunningStats::default(),
+            mid_band_stats: RunningStats::default(),
+            high_band_stats: RunningStats::default(),
+        }
+    }
+
+    pub fn hop_size(&self) -> usize {
+        FEATURE_HOP_SIZE
+    }
+
+    /// Processes exactly one hop (`hop_size()` samples; a short final hop is zero-padded).
+    pub fn process_hop(&mut self, samples: &[f32]) {
+        fill_window_buffer(&mut self.input, samples, &self.window);
+
+        let _ = self.r2c.process(&mut self.input, &mut self.spectrum);
+        let current_magnitudes = magnitudes(&self.spectrum);
+
+        self.centroid_stats
+            .update(spectral_centroid_hz(&current_magnitudes, self.sample_rate));
+        self.rolloff_stats
+            .update(spectral_rolloff_hz(&current_magnitudes, self.sample_rate));
+        self.flatness_stats.update(spectral_flatness(&current_magnitudes));
+        self.flux_stats.update(spectral_flux(
+            &current_magnitudes,
+            self.prev_magnitudes.as_deref(),
+        ));
+        self.zero_crossing_stats.update(zero_crossing_rate(samples));
+
+        let (low_ratio, mid_ratio, high_ratio) =
+            band_energy_ratios(&current_magnitudes, self.sample_rate);
+        self.low_band_stats.update(low_ratio);
+        self.mid_band_stats.update(mid_ratio);
+        self.high_band_stats.update(high_ratio);
+
+        self.prev_magnitudes = Some(current_magnitudes);
+    }
+
+    pub fn finish(&self) -> StemFeatureVector {
+        StemFeatureVector {
+            centroid_hz_mean: self.centroid_stats.mean(),
+            centroid_hz_variance: self.centroid_stats.variance(),
+            rolloff_hz_mean: self.rolloff_stats.mean(),
+            rolloff_hz_variance: self.rolloff_stats.variance(),
+            flatness_mean: self.flatness_stats.mean(),
+            flatness_variance: self.flatness_stats.variance(),
+            flux_mean: self.flux_stats.mean(),
+            flux_variance: self.flux_stats.variance(),
+            zero_crossing_rate_mean: self.zero_crossing_stats.mean(),
+            zero_crossing_rate_variance: self.zero_crossing_stats.variance(),
+            low_band_energy_ratio_mean: self.low_band_stats.mean(),
+            low_band_energy_ratio_variance: self.low_band_stats.variance(),
+            mid_band_energy_ratio_mean: self.mid_band_stats.mean(),
+            mid_band_energy_ratio_variance: self.mid_band_stats.variance(),
+            high_band_energy_ratio_mean: self.high_band_stats.mean(),
+            high_band_energy_ratio_variance: self.high_band_stats.variance(),
+        }
+    }
+}
+
+/// Energy ratios (each 0.0-1.0, summing to ~1.0) falling in three coarse bands: low
+/// (below [`LOW_BAND_HZ`], rumble/bass), mid (between [`LOW_BAND_HZ`] and [`HIGH_BAND_HZ`],
+/// most speech/instrument fundamentals), and high (above [`HIGH_BAND_HZ`], air/sibilance).
+/// Cheaper than a full Bark-band profile but still separates e.g. a bass-heavy music stem
+/// from a high-passed effects stem.
+fn band_energy_ratios(magnitudes: &[f32], sample_rate: u32) -> (f32, f32, f32) {
+    if magnitudes.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let fft_size = (magnitudes.len().saturating_sub(1) * 2).max(1);
+    let hz_per_bin = sample_rate as f32 / fft_size as f32;
+    if hz_per_bin <= EPSILON {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let mut low = 0.0_f32;
+    let mut mid = 0.0_f32;
+    let mut high = 0.0_f32;
+    for (bin, &amp) in magnitudes.iter().enumerate() {
+        let hz = bin as f32 * hz_per_bin;
+        let energy = amp * amp;
+        if hz < LOW_BAND_HZ {
+            low += energy;
+        } else if hz < HIGH_BAND_HZ {
+            mid += energy;
+        } else {
+            high += energy;
+        }
+    }
+
+    let total = low + mid + high;
+    if total <= EPSILON {
+        (0.0, 0.0, 0.0)
+    } else {
+        (low / total, mid / total, high / total)
+    }
+}
+
+/// Fraction of adjacent sample pairs that straddle zero, a cheap time-domain noisiness
+/// proxy that complements the frequency-domain descriptors above.
+fn zero_crossing_rate(samples: &[f32]) -> f32 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+
+    let crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+
+    crossings as f32 / (samples.len() - 1) as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +700,155 @@ mod tests {
         let metrics = analyzer.process_frame(&frame);
         assert!((metrics.dialogue_centroid_hz - tone_hz).abs() < 250.0);
     }
+
+    #[test]
+    fn flatness_is_near_one_for_white_noise_and_near_zero_for_a_tone() {
+        let sample_rate = 48_000;
+        let frame_size = 2048;
+        let mut analyzer = SpectralAnalyzer::new(sample_rate, frame_size);
+
+        let tone_hz = 1_000.0_f32;
+        let tone: Vec<f32> = (0..frame_size)
+            .map(|i| ((2.0 * std::f32::consts::PI * tone_hz * i as f32) / sample_rate as f32).sin())
+            .collect();
+        let frame = SyncedAudioFrame {
+            sample_rate,
+            dialogue_raw: tone,
+            background_raw: vec![0.0; frame_size],
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        assert!(metrics.dialogue_flatness < 0.3);
+    }
+
+    #[test]
+    fn flux_is_zero_on_the_first_frame() {
+        let sample_rate = 48_000;
+        let frame_size = 2048;
+        let mut analyzer = SpectralAnalyzer::new(sample_rate, frame_size);
+
+        let frame = SyncedAudioFrame {
+            sample_rate,
+            dialogue_raw: vec![0.5; frame_size],
+            background_raw: vec![0.0; frame_size],
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        assert_eq!(metrics.dialogue_flux, 0.0);
+    }
+
+    #[test]
+    fn loud_broadband_background_masks_a_quiet_speech_band_tone() {
+        let sample_rate = 48_000;
+        let frame_size = 2048;
+        let mut analyzer = SpectralAnalyzer::new(sample_rate, frame_size);
+
+        let mut seed = 1_u32;
+        let mut next_noise = || {
+            // xorshift32, deterministic so the test doesn't flake.
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed as f32 / u32::MAX as f32) * 2.0 - 1.0
+        };
+        let background: Vec<f32> = (0..frame_size).map(|_| next_noise() * 0.9).collect();
+
+        let tone_hz = 2_000.0_f32;
+        let dialogue: Vec<f32> = (0..frame_size)
+            .map(|i| {
+                0.01 * ((2.0 * std::f32::consts::PI * tone_hz * i as f32) / sample_rate as f32).sin()
+            })
+            .collect();
+
+        let frame = SyncedAudioFrame {
+            sample_rate,
+            dialogue_raw: dialogue,
+            background_raw: background,
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        assert!(metrics.speech_pocket_masked_fraction > 0.5);
+        assert!(metrics.speech_pocket_masked);
+        assert_eq!(metrics.masked_bark_bands.len(), NUM_BARK_BANDS);
+    }
+
+    #[test]
+    fn silent_background_masks_nothing() {
+        let sample_rate = 48_000;
+        let frame_size = 2048;
+        let mut analyzer = SpectralAnalyzer::new(sample_rate, frame_size);
+
+        let tone_hz = 2_000.0_f32;
+        let dialogue: Vec<f32> = (0..frame_size)
+            .map(|i| ((2.0 * std::f32::consts::PI * tone_hz * i as f32) / sample_rate as f32).sin())
+            .collect();
+
+        let frame = SyncedAudioFrame {
+            sample_rate,
+            dialogue_raw: dialogue,
+            background_raw: vec![0.0; frame_size],
+            ..SyncedAudioFrame::default()
+        };
+
+        let metrics = analyzer.process_frame(&frame);
+        assert_eq!(metrics.speech_pocket_masked_fraction, 0.0);
+        assert!(!metrics.speech_pocket_masked);
+    }
+
+    #[test]
+    fn feature_extractor_reports_low_variance_for_a_steady_tone() {
+        let sample_rate = 48_000;
+        let mut extractor = StemFeatureExtractor::new(sample_rate);
+
+        let tone_hz = 1_000.0_f32;
+        for hop_index in 0..8 {
+            let hop: Vec<f32> = (0..extractor.hop_size())
+                .map(|i| {
+                    let t = (hop_index * extractor.hop_size() + i) as f32;
+                    (2.0 * std::f32::consts::PI * tone_hz * t / sample_rate as f32).sin()
+                })
+                .collect();
+            extractor.process_hop(&hop);
+        }
+
+        let features = extractor.finish();
+        assert!((features.centroid_hz_mean - tone_hz).abs() < 250.0);
+        assert!(features.centroid_hz_variance < 1_000.0);
+        assert!(features.flatness_mean < 0.3);
+        // A 1kHz tone's energy should land almost entirely in the mid band.
+        assert!(features.mid_band_energy_ratio_mean > 0.9);
+    }
+
+    #[test]
+    fn fingerprint_distance_is_zero_for_identical_fingerprints_and_positive_otherwise() {
+        let sample_rate = 48_000;
+        let mut low_extractor = StemFeatureExtractor::new(sample_rate);
+        let mut high_extractor = StemFeatureExtractor::new(sample_rate);
+
+        for hop_index in 0..4 {
+            let low_hop: Vec<f32> = (0..low_extractor.hop_size())
+                .map(|i| {
+                    let t = (hop_index * low_extractor.hop_size() + i) as f32;
+                    (2.0 * std::f32::consts::PI * 200.0 * t / sample_rate as f32).sin()
+                })
+                .collect();
+            let high_hop: Vec<f32> = (0..high_extractor.hop_size())
+                .map(|i| {
+                    let t = (hop_index * high_extractor.hop_size() + i) as f32;
+                    (2.0 * std::f32::consts::PI * 8_000.0 * t / sample_rate as f32).sin()
+                })
+                .collect();
+            low_extractor.process_hop(&low_hop);
+            high_extractor.process_hop(&high_hop);
+        }
+
+        let low_fingerprint = low_extractor.finish();
+        let high_fingerprint = high_extractor.finish();
+
+        assert_eq!(fingerprint_distance(&low_fingerprint, &low_fingerprint), 0.0);
+        assert!(fingerprint_distance(&low_fingerprint, &high_fingerprint) > 0.1);
+    }
 }
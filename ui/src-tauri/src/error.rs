@@ -0,0 +1,127 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Stable, machine-readable error taxonomy for `#[tauri::command]` results.
+///
+/// Each variant carries a `code()` that the frontend can safely `switch` on
+/// (it will not change across releases) plus a human-readable `message()`
+/// for logging/display. Recoverable conditions (bad user input, a missing
+/// file, a malformed JSON payload, an I/O failure writing a project file)
+/// should map to `InvalidArgument`/`NotFound`/`Timeout`/`Internal`; only
+/// faults that mean the backend itself is broken (poisoned lock, unreachable
+/// project root, unexpected process death) should map to `Fatal`.
+#[derive(Debug, Clone)]
+pub enum MikupError {
+    InvalidArgument(String),
+    NotFound(String),
+    PipelineFailed {
+        exit_code: Option<i32>,
+        message: String,
+    },
+    Timeout(String),
+    Internal(String),
+    /// Distinct from `Internal`: reserved for faults that mean the backend
+    /// process itself can no longer be trusted (poisoned lock, unresolvable
+    /// project root, unexpected sidecar death) rather than an ordinary
+    /// recoverable failure. See [`MikupError::is_fatal`].
+    Fatal(String),
+}
+
+impl MikupError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::NotFound(_) => "not_found",
+            Self::PipelineFailed { .. } => "pipeline_failed",
+            Self::Timeout(_) => "timeout",
+            Self::Internal(_) => "internal",
+            Self::Fatal(_) => "fatal",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::InvalidArgument(message)
+            | Self::NotFound(message)
+            | Self::Timeout(message)
+            | Self::Internal(message)
+            | Self::Fatal(message) => message.clone(),
+            Self::PipelineFailed { exit_code, message } => {
+                format!("{message} (exit code {exit_code:?})")
+            }
+        }
+    }
+
+    /// True for faults that mean the backend itself is broken (poisoned lock,
+    /// unreachable project root, unexpected process death) rather than a
+    /// recoverable, user-facing condition. [`MikupResponse::from_result`] uses
+    /// this to pick `Fatal` over `Failure` without every call site having to
+    /// classify its own errors. `Internal` is the catch-all for ordinary
+    /// recoverable failures (missing files, bad JSON, I/O errors) and is
+    /// deliberately *not* fatal — only the dedicated `Fatal` variant is.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Fatal(_))
+    }
+}
+
+impl std::fmt::Display for MikupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+impl std::error::Error for MikupError {}
+
+/// Existing helpers return `Result<_, String>`; treat a bare string as an
+/// internal fault unless the caller has already classified it.
+impl From<String> for MikupError {
+    fn from(message: String) -> Self {
+        Self::Internal(message)
+    }
+}
+
+impl Serialize for MikupError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("MikupError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.message())?;
+        state.end()
+    }
+}
+
+/// Tagged-union envelope every `#[tauri::command]` returns instead of a bare
+/// `Result<_, String>`, so the frontend can branch on `type` (retry a
+/// `Failure`, abort and surface a `Fatal`) rather than pattern-matching on
+/// error prose.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "content")]
+pub enum MikupResponse<T> {
+    Success(T),
+    Failure(MikupError),
+    Fatal(MikupError),
+}
+
+impl<T> MikupResponse<T> {
+    pub fn success(value: T) -> Self {
+        Self::Success(value)
+    }
+
+    pub fn failure(error: MikupError) -> Self {
+        Self::Failure(error)
+    }
+
+    pub fn fatal(error: MikupError) -> Self {
+        Self::Fatal(error)
+    }
+
+    /// Maps a `Result` into `Success`/`Failure`/`Fatal`, routing the error
+    /// through [`MikupError::is_fatal`] so callers don't have to classify
+    /// their own errors at each `#[tauri::command]` site.
+    pub fn from_result(result: Result<T, MikupError>) -> Self {
+        match result {
+            Ok(value) => Self::Success(value),
+            Err(error) if error.is_fatal() => Self::Fatal(error),
+            Err(error) => Self::Failure(error),
+        }
+    }
+}
@@ -8,25 +8,33 @@ use tauri::Manager;
 use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
 
-use crate::dsp::loudness::LoudnessAnalyzer;
-use crate::dsp::player::{interleave_mono, AudioOutputPlayer, MonoResampler};
-use crate::dsp::scanner::{OfflineLoudnessScanner, ScanEvent};
-use crate::dsp::spatial::SpatialAnalyzer;
-use crate::dsp::spectral::SpectralAnalyzer;
+use crate::agent_sidecar::AgentSidecarHandle;
+use crate::audio_actor::{AudioActorHandle, AudioCommand};
+use crate::dsp::scanner::{OfflineLoudnessScanner, ScanEvent, CANONICAL_STEMS};
 use crate::dsp::{shared_default_stem_states, MikupAudioDecoder, StemState};
+use crate::error::{MikupError, MikupResponse};
+use crate::metrics_broadcast::MetricsBroadcastHandle;
+use crate::transcription::TranscriptionHandle;
 
+pub mod agent_sidecar;
+pub mod audio_actor;
 pub mod dsp;
+pub mod error;
+pub mod metrics_broadcast;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+pub mod transcription;
 
 const DSP_FRAME_SIZE: usize = 2048;
 const DSP_SAMPLE_RATE: u32 = 48_000;
 /// Maximum Lissajous points to send per frame (subsampled from the raw 2048-sample frame).
-const LISSAJOUS_MAX_POINTS: usize = 128;
+pub(crate) const LISSAJOUS_MAX_POINTS: usize = 128;
 /// Minimum wall-clock interval between emitted frames; guards against render-cycle flooding
 /// if a caller ever uses a smaller frame size than the default 2048/48kHz (~42 ms/frame).
-const MIN_EMIT_INTERVAL_MS: u64 = 16;
+pub(crate) const MIN_EMIT_INTERVAL_MS: u64 = 16;
 
 #[derive(Clone, serde::Serialize)]
-struct ProgressPayload {
+pub(crate) struct ProgressPayload {
     stage: String,
     progress: u32,
     message: String,
@@ -35,7 +43,7 @@ struct ProgressPayload {
 /// Per-frame payload streamed via the `dsp-frame` Tauri event.
 /// All float fields use f32 for compact JSON; the frontend rounds as needed.
 #[derive(Clone, serde::Serialize)]
-struct DspFramePayload {
+pub(crate) struct DspFramePayload {
     /// Monotonic counter (1-based) of frames processed so far.
     frame_index: u64,
     /// Elapsed time in seconds at the start of this frame.
@@ -58,15 +66,25 @@ struct DspFramePayload {
     dialogue_centroid_hz: f32,
     background_centroid_hz: f32,
     speech_pocket_masked: bool,
+    /// Per-Bark-band masked flags from the critical-band masking model (lowest band first).
+    masked_bark_bands: Vec<bool>,
+    /// Fraction of 1-4 kHz Bark bands currently masked by the background.
+    speech_pocket_masked_fraction: f32,
     dialogue_speech_energy: f32,
     background_speech_energy: f32,
     snr_db: f32,
+    dialogue_flux: f32,
+    background_flux: f32,
+    dialogue_rolloff_hz: f32,
+    background_rolloff_hz: f32,
+    dialogue_flatness: f32,
+    background_flatness: f32,
 }
 
 /// Emitted once via `dsp-complete` when the decoder naturally reaches EOF.
 /// Contains integrated (whole-file) metrics suitable for writing to mikup_payload.json.
 #[derive(Clone, serde::Serialize)]
-struct DspCompletePayload {
+pub(crate) struct DspCompletePayload {
     total_frames: u64,
     dialogue_integrated_lufs: f32,
     dialogue_loudness_range_lu: f32,
@@ -77,12 +95,21 @@ struct DspCompletePayload {
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct AppConfig {
     default_projects_dir: String,
+    /// Pushgateway endpoint for the optional `metrics` feature, e.g. `http://localhost:9091`.
+    /// Ignored when the feature is not compiled in or left empty.
+    #[serde(default)]
+    metrics_pushgateway_url: Option<String>,
+    /// Job label reported alongside pushed metrics. Defaults to `"mikup"` if unset.
+    #[serde(default)]
+    metrics_job: Option<String>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             default_projects_dir: String::new(),
+            metrics_pushgateway_url: None,
+            metrics_job: None,
         }
     }
 }
@@ -97,14 +124,16 @@ fn contains_unsafe_shell_tokens(value: &str) -> bool {
     value.contains('`') || value.contains('\n') || value.contains('\r')
 }
 
-fn ensure_safe_argument(name: &str, value: &str) -> Result<(), String> {
+fn ensure_safe_argument(name: &str, value: &str) -> Result<(), MikupError> {
     if value.trim().is_empty() {
-        return Err(format!("{name} must not be empty"));
+        return Err(MikupError::InvalidArgument(format!(
+            "{name} must not be empty"
+        )));
     }
     if contains_unsafe_shell_tokens(value) {
-        return Err(format!(
+        return Err(MikupError::InvalidArgument(format!(
             "{name} contains disallowed shell operator characters"
-        ));
+        )));
     }
     Ok(())
 }
@@ -157,11 +186,13 @@ fn resolve_python_path(project_root: &Path) -> String {
 
 fn resolve_output_paths(
     output_directory: &str,
-) -> Result<(PathBuf, String, PathBuf, String), String> {
+) -> Result<(PathBuf, String, PathBuf, String), MikupError> {
     ensure_safe_argument("Output directory", output_directory)?;
     let output_directory_path = PathBuf::from(output_directory);
     if !output_directory_path.is_absolute() {
-        return Err("Output directory must be an absolute path".to_string());
+        return Err(MikupError::InvalidArgument(
+            "Output directory must be an absolute path".to_string(),
+        ));
     }
     let output_directory_arg = output_directory_path.to_string_lossy().into_owned();
     ensure_safe_argument("Output directory", &output_directory_arg)?;
@@ -178,11 +209,21 @@ fn resolve_output_paths(
     ))
 }
 
-fn resolve_data_artifact_path(output_directory: &str, file_name: &str) -> Result<PathBuf, String> {
+fn resolve_data_artifact_path(
+    output_directory: &str,
+    file_name: &str,
+) -> Result<PathBuf, MikupError> {
     ensure_safe_argument("Output directory", output_directory)?;
     Ok(PathBuf::from(output_directory).join("data").join(file_name))
 }
 
+/// Looks up the project root or classifies its absence as a `Fatal` fault —
+/// this is not a user-correctable condition, so it never maps to `Failure`.
+fn require_project_root(app: &tauri::AppHandle) -> Result<PathBuf, MikupError> {
+    find_project_root(app)
+        .ok_or_else(|| MikupError::Fatal("Unable to resolve project root".to_string()))
+}
+
 fn app_config_path(project_root: &Path) -> PathBuf {
     project_root.join("data").join("config.json")
 }
@@ -204,12 +245,21 @@ fn build_base_pipeline_args(
     ]
 }
 
+/// Identifies the batch item a pipeline run belongs to, so [`run_python_pipeline`] can
+/// mirror its per-stage `process-status` progress into a `batch-progress` event as well.
+struct BatchProgressContext<'a> {
+    current_index: usize,
+    total: usize,
+    current_file: &'a str,
+}
+
 async fn run_python_pipeline(
     app: &tauri::AppHandle,
     project_root: &Path,
     args: Vec<String>,
     timeout_secs: u64,
-) -> Result<(), String> {
+    batch_context: Option<&BatchProgressContext<'_>>,
+) -> Result<(), MikupError> {
     let python_path = resolve_python_path(project_root);
     let (mut rx, _child) = app
         .shell()
@@ -217,7 +267,7 @@ async fn run_python_pipeline(
         .current_dir(project_root)
         .args(args)
         .spawn()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| MikupError::Internal(e.to_string()))?;
 
     let mut stdout_buf = String::new();
     let mut clean_exit = false;
@@ -227,7 +277,9 @@ async fn run_python_pipeline(
         let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
         let maybe_event = tokio::time::timeout(remaining, rx.recv())
             .await
-            .map_err(|_| format!("Pipeline timed out after {timeout_secs} seconds"))?;
+            .map_err(|_| {
+                MikupError::Timeout(format!("Pipeline timed out after {timeout_secs} seconds"))
+            })?;
 
         match maybe_event {
             Some(CommandEvent::Stdout(chunk)) => {
@@ -237,14 +289,29 @@ async fn run_python_pipeline(
                     let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
                     if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(trimmed) {
                         if json_val["type"] == "progress" {
+                            let stage = json_val["stage"].as_str().unwrap_or("").to_string();
+                            let progress = json_val["progress"].as_u64().unwrap_or(0) as u32;
+                            let message = json_val["message"].as_str().unwrap_or("").to_string();
                             let _ = app.emit(
                                 "process-status",
                                 ProgressPayload {
-                                    stage: json_val["stage"].as_str().unwrap_or("").to_string(),
-                                    progress: json_val["progress"].as_u64().unwrap_or(0) as u32,
-                                    message: json_val["message"].as_str().unwrap_or("").to_string(),
+                                    stage: stage.clone(),
+                                    progress,
+                                    message,
                                 },
                             );
+                            if let Some(ctx) = batch_context {
+                                let _ = app.emit(
+                                    "batch-progress",
+                                    BatchProgressPayload {
+                                        current_index: ctx.current_index,
+                                        total: ctx.total,
+                                        current_file: ctx.current_file.to_string(),
+                                        stage,
+                                        item_progress: progress,
+                                    },
+                                );
+                            }
                         }
                     }
                 }
@@ -256,7 +323,10 @@ async fn run_python_pipeline(
             }
             Some(CommandEvent::Terminated(status)) => {
                 if status.code != Some(0) {
-                    return Err(format!("Pipeline failed with exit code {:?}", status.code));
+                    return Err(MikupError::PipelineFailed {
+                        exit_code: status.code,
+                        message: "Pipeline exited with a non-zero status".to_string(),
+                    });
                 }
                 clean_exit = true;
                 break;
@@ -267,16 +337,16 @@ async fn run_python_pipeline(
     }
 
     if !clean_exit {
-        return Err("Pipeline terminated unexpectedly".to_string());
+        return Err(MikupError::Internal(
+            "Pipeline terminated unexpectedly".to_string(),
+        ));
     }
 
     Ok(())
 }
 
-#[tauri::command]
-async fn get_history(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
+async fn get_history_impl(app: tauri::AppHandle) -> Result<serde_json::Value, MikupError> {
+    let project_root = require_project_root(&app)?;
     let history_path = project_root.join("data/history.json");
 
     if !history_path.exists() {
@@ -285,98 +355,136 @@ async fn get_history(app: tauri::AppHandle) -> Result<serde_json::Value, String>
 
     let content = tokio::fs::read_to_string(history_path)
         .await
-        .map_err(|e| e.to_string())?;
-    let history: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        .map_err(|e| MikupError::Internal(e.to_string()))?;
+    let history: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| MikupError::Internal(e.to_string()))?;
     Ok(history)
 }
 
 #[tauri::command]
-async fn get_app_config(app: tauri::AppHandle) -> Result<AppConfig, String> {
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
+async fn get_history(app: tauri::AppHandle) -> MikupResponse<serde_json::Value> {
+    MikupResponse::from_result(get_history_impl(app).await)
+}
+
+async fn get_app_config_impl(app: tauri::AppHandle) -> Result<AppConfig, MikupError> {
+    let project_root = require_project_root(&app)?;
     let config_path = app_config_path(&project_root);
 
     let content = match tokio::fs::read_to_string(config_path).await {
         Ok(c) => c,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(AppConfig::default()),
-        Err(e) => return Err(format!("Failed to read app config: {e}")),
+        Err(e) => {
+            return Err(MikupError::Internal(format!(
+                "Failed to read app config: {e}"
+            )))
+        }
     };
 
-    serde_json::from_str::<AppConfig>(&content).map_err(|e| format!("Invalid app config JSON: {e}"))
+    serde_json::from_str::<AppConfig>(&content)
+        .map_err(|e| MikupError::Internal(format!("Invalid app config JSON: {e}")))
 }
 
 #[tauri::command]
-async fn set_default_projects_dir(
+async fn get_app_config(app: tauri::AppHandle) -> MikupResponse<AppConfig> {
+    MikupResponse::from_result(get_app_config_impl(app).await)
+}
+
+/// Pushes the current telemetry snapshot if `metrics_pushgateway_url` is configured.
+/// A no-op (and costs nothing beyond one config read) whenever the `metrics` feature is
+/// disabled or the user hasn't set a pushgateway URL — telemetry is opt-in, not assumed.
+#[cfg(feature = "metrics")]
+async fn push_telemetry_if_configured(app: &tauri::AppHandle) {
+    let Ok(config) = get_app_config_impl(app.clone()).await else {
+        return;
+    };
+    if let Some(url) = config.metrics_pushgateway_url {
+        let job = config.metrics_job.unwrap_or_else(|| "mikup".to_string());
+        telemetry::push(url, job);
+    }
+}
+
+async fn set_default_projects_dir_impl(
     app: tauri::AppHandle,
     path: String,
-) -> Result<AppConfig, String> {
+) -> Result<AppConfig, MikupError> {
     ensure_safe_argument("Default projects directory", &path)?;
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
+    let project_root = require_project_root(&app)?;
 
     let config_path = app_config_path(&project_root);
     let config_dir = config_path
         .parent()
-        .ok_or_else(|| "Invalid app config path".to_string())?;
+        .ok_or_else(|| MikupError::Internal("Invalid app config path".to_string()))?;
     tokio::fs::create_dir_all(config_dir)
         .await
-        .map_err(|e| format!("Failed to create config directory: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to create config directory: {e}")))?;
 
     let normalized_path = PathBuf::from(path).to_string_lossy().into_owned();
     let config = AppConfig {
         default_projects_dir: normalized_path,
+        ..get_app_config_impl(app.clone()).await.unwrap_or_default()
     };
     let serialized = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to serialize config: {e}")))?;
     tokio::fs::write(config_path, serialized)
         .await
-        .map_err(|e| format!("Failed to write app config: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to write app config: {e}")))?;
 
     Ok(config)
 }
 
 #[tauri::command]
-async fn setup_project_workspace(
+async fn set_default_projects_dir(app: tauri::AppHandle, path: String) -> MikupResponse<AppConfig> {
+    MikupResponse::from_result(set_default_projects_dir_impl(app, path).await)
+}
+
+async fn setup_project_workspace_impl(
     input_path: String,
     base_directory: String,
-) -> Result<WorkspaceSetupResult, String> {
+) -> Result<WorkspaceSetupResult, MikupError> {
     ensure_safe_argument("Input path", &input_path)?;
     ensure_safe_argument("Base directory", &base_directory)?;
 
     let base_dir_path = PathBuf::from(&base_directory);
     if !base_dir_path.is_absolute() {
-        return Err("Base directory must be an absolute path".to_string());
+        return Err(MikupError::InvalidArgument(
+            "Base directory must be an absolute path".to_string(),
+        ));
     }
 
     let input_file = PathBuf::from(&input_path);
     if !input_file.is_file() {
-        return Err(format!("Input file not found: {input_path}"));
+        return Err(MikupError::NotFound(format!(
+            "Input file not found: {input_path}"
+        )));
     }
 
-    let file_stem = input_file
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| "Failed to extract file stem from input path".to_string())?;
+    let file_stem = input_file.file_stem().and_then(|s| s.to_str()).ok_or_else(|| {
+        MikupError::InvalidArgument("Failed to extract file stem from input path".to_string())
+    })?;
     let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
     let workspace_name = format!("{file_stem}_{timestamp}");
     let workspace_dir = PathBuf::from(base_directory).join(workspace_name);
     let data_dir = workspace_dir.join("data");
     let stems_dir = workspace_dir.join("stems");
 
-    tokio::fs::create_dir_all(&data_dir)
-        .await
-        .map_err(|e| format!("Failed to create workspace data directory: {e}"))?;
-    tokio::fs::create_dir_all(&stems_dir)
-        .await
-        .map_err(|e| format!("Failed to create workspace stems directory: {e}"))?;
-
-    let input_file_name = input_file
-        .file_name()
-        .ok_or_else(|| "Failed to extract input filename".to_string())?;
+    tokio::fs::create_dir_all(&data_dir).await.map_err(|e| {
+        MikupError::Internal(format!("Failed to create workspace data directory: {e}"))
+    })?;
+    tokio::fs::create_dir_all(&stems_dir).await.map_err(|e| {
+        MikupError::Internal(format!(
+            "Failed to create workspace stems directory: {e}"
+        ))
+    })?;
+
+    let input_file_name = input_file.file_name().ok_or_else(|| {
+        MikupError::InvalidArgument("Failed to extract input filename".to_string())
+    })?;
     let copied_input_path = workspace_dir.join(input_file_name);
     tokio::fs::copy(&input_file, &copied_input_path)
         .await
-        .map_err(|e| format!("Failed to copy source audio into workspace: {e}"))?;
+        .map_err(|e| {
+            MikupError::Internal(format!("Failed to copy source audio into workspace: {e}"))
+        })?;
 
     Ok(WorkspaceSetupResult {
         workspace_dir: workspace_dir.to_string_lossy().into_owned(),
@@ -385,53 +493,207 @@ async fn setup_project_workspace(
 }
 
 #[tauri::command]
-async fn process_audio(
-    app: tauri::AppHandle,
+async fn setup_project_workspace(
     input_path: String,
-    output_directory: String,
-) -> Result<String, String> {
-    ensure_safe_argument("Input path", &input_path)?;
+    base_directory: String,
+) -> MikupResponse<WorkspaceSetupResult> {
+    MikupResponse::from_result(setup_project_workspace_impl(input_path, base_directory).await)
+}
+
+async fn process_audio_core(
+    app: &tauri::AppHandle,
+    input_path: &str,
+    output_directory: &str,
+    batch_context: Option<&BatchProgressContext<'_>>,
+) -> Result<String, MikupError> {
+    ensure_safe_argument("Input path", input_path)?;
 
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
+    let project_root = require_project_root(app)?;
     let (output_directory_path, output_directory_arg, output_path, output_path_arg) =
-        resolve_output_paths(&output_directory)?;
+        resolve_output_paths(output_directory)?;
     tokio::fs::create_dir_all(&output_directory_path)
         .await
-        .map_err(|e| format!("Failed to create output directory: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to create output directory: {e}")))?;
 
     let input_path_arg = PathBuf::from(input_path).to_string_lossy().into_owned();
     ensure_safe_argument("Input path", &input_path_arg)?;
     let args = build_base_pipeline_args(&input_path_arg, &output_directory_arg, &output_path_arg);
 
-    run_python_pipeline(&app, &project_root, args, 600).await?;
+    run_python_pipeline(app, &project_root, args, 600, batch_context).await?;
 
     let payload = tokio::fs::read_to_string(output_path)
         .await
-        .map_err(|e| format!("Failed to read payload: {}", e))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to read payload: {e}")))?;
 
     Ok(payload)
 }
 
+async fn process_audio_impl(
+    app: tauri::AppHandle,
+    input_path: String,
+    output_directory: String,
+) -> Result<String, MikupError> {
+    process_audio_core(&app, &input_path, &output_directory, None).await
+}
+
 #[tauri::command]
-async fn run_pipeline_stage(
+async fn process_audio(
+    app: tauri::AppHandle,
+    input_path: String,
+    output_directory: String,
+) -> MikupResponse<String> {
+    MikupResponse::from_result(process_audio_impl(app, input_path, output_directory).await)
+}
+
+/// Per-item progress carried by the `batch-progress` event emitted during
+/// [`process_audio_batch`], so the frontend can render both per-item and overall completion.
+#[derive(Clone, serde::Serialize)]
+struct BatchProgressPayload {
+    current_index: usize,
+    total: usize,
+    current_file: String,
+    stage: String,
+    item_progress: u32,
+}
+
+/// Outcome of a single input within a [`process_audio_batch`] run.
+#[derive(Clone, serde::Serialize)]
+struct BatchItemResult {
+    input_path: String,
+    workspace_dir: Option<String>,
+    success: bool,
+    error: Option<MikupError>,
+}
+
+/// Returned by [`process_audio_batch`] once every item has been attempted (or the run
+/// stopped early because `fail_fast` was set).
+#[derive(Clone, serde::Serialize)]
+struct BatchSummary {
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    results: Vec<BatchItemResult>,
+}
+
+async fn process_one_batch_item(
+    app: &tauri::AppHandle,
+    input_path: &str,
+    base_directory: &str,
+    current_index: usize,
+    total: usize,
+) -> Result<String, MikupError> {
+    let workspace =
+        setup_project_workspace_impl(input_path.to_string(), base_directory.to_string()).await?;
+    let batch_context = BatchProgressContext {
+        current_index,
+        total,
+        current_file: input_path,
+    };
+    process_audio_core(
+        app,
+        &workspace.copied_input_path,
+        &workspace.workspace_dir,
+        Some(&batch_context),
+    )
+    .await?;
+    Ok(workspace.workspace_dir)
+}
+
+/// Runs `setup_project_workspace` + the Python pipeline for each input in `input_paths`,
+/// one workspace per file, sequentially. Unlike [`process_audio`], a per-item failure is
+/// recorded in the returned [`BatchSummary`] rather than aborting the whole run — unless
+/// `fail_fast` is set, in which case the batch stops at the first failing item.
+async fn process_audio_batch_impl(
+    app: tauri::AppHandle,
+    input_paths: Vec<String>,
+    base_directory: String,
+    fail_fast: Option<bool>,
+) -> Result<BatchSummary, MikupError> {
+    ensure_safe_argument("Base directory", &base_directory)?;
+    if input_paths.is_empty() {
+        return Err(MikupError::InvalidArgument(
+            "input_paths must not be empty".to_string(),
+        ));
+    }
+    let fail_fast = fail_fast.unwrap_or(false);
+    let total = input_paths.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (current_index, input_path) in input_paths.into_iter().enumerate() {
+        let _ = app.emit(
+            "batch-progress",
+            BatchProgressPayload {
+                current_index,
+                total,
+                current_file: input_path.clone(),
+                stage: "starting".to_string(),
+                item_progress: 0,
+            },
+        );
+
+        match process_one_batch_item(&app, &input_path, &base_directory, current_index, total)
+            .await
+        {
+            Ok(workspace_dir) => results.push(BatchItemResult {
+                input_path,
+                workspace_dir: Some(workspace_dir),
+                success: true,
+                error: None,
+            }),
+            Err(error) => {
+                results.push(BatchItemResult {
+                    input_path,
+                    workspace_dir: None,
+                    success: false,
+                    error: Some(error),
+                });
+                if fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let failed = results.len() - succeeded;
+
+    Ok(BatchSummary {
+        total,
+        succeeded,
+        failed,
+        results,
+    })
+}
+
+#[tauri::command]
+async fn process_audio_batch(
+    app: tauri::AppHandle,
+    input_paths: Vec<String>,
+    base_directory: String,
+    fail_fast: Option<bool>,
+) -> MikupResponse<BatchSummary> {
+    MikupResponse::from_result(
+        process_audio_batch_impl(app, input_paths, base_directory, fail_fast).await,
+    )
+}
+
+async fn run_pipeline_stage_impl(
     app: tauri::AppHandle,
     input_path: String,
     output_directory: String,
     stage: String,
     fast_mode: Option<bool>,
     force: Option<bool>,
-) -> Result<String, String> {
+) -> Result<String, MikupError> {
     ensure_safe_argument("Input path", &input_path)?;
     ensure_safe_argument("Stage", &stage)?;
 
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
+    let project_root = require_project_root(&app)?;
     let (output_directory_path, output_directory_arg, _output_path, output_path_arg) =
         resolve_output_paths(&output_directory)?;
     tokio::fs::create_dir_all(&output_directory_path)
         .await
-        .map_err(|e| format!("Failed to create output directory: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to create output directory: {e}")))?;
 
     let input_path_arg = PathBuf::from(input_path).to_string_lossy().into_owned();
     ensure_safe_argument("Input path", &input_path_arg)?;
@@ -441,10 +703,10 @@ async fn run_pipeline_stage(
         "separation" | "transcription" | "dsp" | "semantics" | "director"
     );
     if !valid_stage {
-        return Err(format!(
+        return Err(MikupError::InvalidArgument(format!(
             "Invalid stage '{}'. Allowed stages: separation, transcription, dsp, semantics, director",
             stage
-        ));
+        )));
     }
 
     let mut args =
@@ -457,42 +719,77 @@ async fn run_pipeline_stage(
         args.push("--force".to_string());
     }
 
-    run_python_pipeline(&app, &project_root, args, 1200).await?;
+    #[cfg(feature = "metrics")]
+    let stage_start = std::time::Instant::now();
+
+    run_python_pipeline(&app, &project_root, args, 1200, None).await?;
+
+    #[cfg(feature = "metrics")]
+    {
+        telemetry::record_stage_duration(&stage_arg, stage_start.elapsed());
+        push_telemetry_if_configured(&app).await;
+    }
+
     Ok(format!("Stage {stage_arg} completed"))
 }
 
 #[tauri::command]
-async fn read_output_payload(output_directory: String) -> Result<String, String> {
+async fn run_pipeline_stage(
+    app: tauri::AppHandle,
+    input_path: String,
+    output_directory: String,
+    stage: String,
+    fast_mode: Option<bool>,
+    force: Option<bool>,
+) -> MikupResponse<String> {
+    MikupResponse::from_result(
+        run_pipeline_stage_impl(app, input_path, output_directory, stage, fast_mode, force).await,
+    )
+}
+
+async fn read_output_payload_impl(output_directory: String) -> Result<String, MikupError> {
     let (_output_directory_path, _output_directory_arg, output_path, _output_path_arg) =
         resolve_output_paths(&output_directory)?;
     tokio::fs::read_to_string(output_path)
         .await
-        .map_err(|e| format!("Failed to read payload: {e}"))
+        .map_err(|e| MikupError::Internal(format!("Failed to read payload: {e}")))
 }
 
 #[tauri::command]
-async fn get_stems(output_directory: String) -> Result<serde_json::Value, String> {
+async fn read_output_payload(output_directory: String) -> MikupResponse<String> {
+    MikupResponse::from_result(read_output_payload_impl(output_directory).await)
+}
+
+async fn get_stems_impl(output_directory: String) -> Result<serde_json::Value, MikupError> {
     let stems_path = resolve_data_artifact_path(&output_directory, "stems.json")?;
 
     if !stems_path.exists() {
-        return Err(format!("stems.json not found at {}", stems_path.display()));
+        return Err(MikupError::NotFound(format!(
+            "stems.json not found at {}",
+            stems_path.display()
+        )));
     }
 
     let content = tokio::fs::read_to_string(&stems_path)
         .await
-        .map_err(|e| format!("Failed to read stems.json: {e}"))?;
+        .map_err(|e| MikupError::Internal(format!("Failed to read stems.json: {e}")))?;
 
-    serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in stems.json: {e}"))
+    serde_json::from_str(&content)
+        .map_err(|e| MikupError::Internal(format!("Invalid JSON in stems.json: {e}")))
 }
 
 #[tauri::command]
-async fn get_pipeline_state(output_directory: String) -> Result<u32, String> {
+async fn get_stems(output_directory: String) -> MikupResponse<serde_json::Value> {
+    MikupResponse::from_result(get_stems_impl(output_directory).await)
+}
+
+async fn get_pipeline_state_impl(output_directory: String) -> Result<u32, MikupError> {
     let state_path = resolve_data_artifact_path(&output_directory, "stage_state.json")?;
 
     let content = match tokio::fs::read_to_string(&state_path).await {
         Ok(c) => c,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(MikupError::Internal(e.to_string())),
     };
 
     let state: serde_json::Value = match serde_json::from_str(&content) {
@@ -529,17 +826,21 @@ async fn get_pipeline_state(output_directory: String) -> Result<u32, String> {
     Ok(count)
 }
 
+#[tauri::command]
+async fn get_pipeline_state(output_directory: String) -> MikupResponse<u32> {
+    MikupResponse::from_result(get_pipeline_state_impl(output_directory).await)
+}
+
 /// Persist the integrated LUFS and LRA produced by `stream_audio_metrics` to disk.
 /// Written to `{output_directory}/data/dsp_metrics.json` so the Python backend can
 /// read it during Stage 5 (AI Director report generation).
-#[tauri::command]
-async fn write_dsp_metrics(
+async fn write_dsp_metrics_impl(
     output_directory: String,
     dialogue_integrated_lufs: f32,
     dialogue_loudness_range_lu: f32,
     background_integrated_lufs: f32,
     background_loudness_range_lu: f32,
-) -> Result<(), String> {
+) -> Result<(), MikupError> {
     let metrics_path = resolve_data_artifact_path(&output_directory, "dsp_metrics.json")?;
 
     let metrics = serde_json::json!({
@@ -549,18 +850,39 @@ async fn write_dsp_metrics(
         "background_loudness_range_lu": background_loudness_range_lu,
     });
 
-    let serialized = serde_json::to_string_pretty(&metrics).map_err(|e| e.to_string())?;
+    let serialized = serde_json::to_string_pretty(&metrics)
+        .map_err(|e| MikupError::Internal(e.to_string()))?;
 
     // Ensure the data directory exists (workspace setup normally creates it, but be safe).
     if let Some(parent) = metrics_path.parent() {
         tokio::fs::create_dir_all(parent)
             .await
-            .map_err(|e| format!("Failed to create data directory: {e}"))?;
+            .map_err(|e| MikupError::Internal(format!("Failed to create data directory: {e}")))?;
     }
 
     tokio::fs::write(&metrics_path, serialized)
         .await
-        .map_err(|e| format!("Failed to write dsp_metrics.json: {e}"))
+        .map_err(|e| MikupError::Internal(format!("Failed to write dsp_metrics.json: {e}")))
+}
+
+#[tauri::command]
+async fn write_dsp_metrics(
+    output_directory: String,
+    dialogue_integrated_lufs: f32,
+    dialogue_loudness_range_lu: f32,
+    background_integrated_lufs: f32,
+    background_loudness_range_lu: f32,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(
+        write_dsp_metrics_impl(
+            output_directory,
+            dialogue_integrated_lufs,
+            dialogue_loudness_range_lu,
+            background_integrated_lufs,
+            background_loudness_range_lu,
+        )
+        .await,
+    )
 }
 
 /// Build a static LUFS map offline using fast Rust decoding + EBU R128, then persist to disk.
@@ -568,16 +890,18 @@ async fn write_dsp_metrics(
 /// The returned JSON is shaped as `{ "lufs_graph": { ... } }` so callers can merge it into
 /// `payload.metrics`. We additionally persist compatibility flat fields in `dsp_metrics.json`
 /// so existing Stage 5 readers can continue reading integrated LUFS values.
-#[tauri::command]
-async fn generate_static_map(
+async fn generate_static_map_impl(
     app: tauri::AppHandle,
     output_directory: String,
     stem_paths: HashMap<String, String>,
-) -> Result<serde_json::Value, String> {
+    max_analysis_samplerate: Option<u32>,
+) -> Result<serde_json::Value, MikupError> {
     ensure_safe_argument("Output directory", &output_directory)?;
     let output_path = PathBuf::from(&output_directory);
     if !output_path.is_absolute() {
-        return Err("Output directory must be an absolute path".to_string());
+        return Err(MikupError::InvalidArgument(
+            "Output directory must be an absolute path".to_string(),
+        ));
     }
 
     for (stem, path) in &stem_paths {
@@ -587,20 +911,45 @@ async fn generate_static_map(
 
     let app_handle = app.clone();
     let output_directory_for_write = output_directory.clone();
-    let scan_result = tokio::task::spawn_blocking(move || -> Result<serde_json::Value, String> {
-        let scanner = OfflineLoudnessScanner::new(2).map_err(|e| e.to_string())?;
+    let scan_result = tokio::task::spawn_blocking(
+        move || -> Result<serde_json::Value, MikupError> {
+        let scanner = OfflineLoudnessScanner::new(2, max_analysis_samplerate)
+            .map_err(|e| MikupError::Internal(e.to_string()))?;
         let resolved = OfflineLoudnessScanner::resolve_required_stems(&stem_paths)
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| MikupError::InvalidArgument(e.to_string()))?;
 
+        // Per-stem (expected_duration_secs, scanned_secs), used to compute a weighted overall
+        // percentage instead of naive completed_stems/5 — stems run concurrently now and can
+        // finish in any order or take wildly different amounts of time.
+        let mut stem_progress: HashMap<String, (f32, f32)> = HashMap::new();
         let mut completed_stems = 0_u32;
+        let weighted_percent = |stem_progress: &HashMap<String, (f32, f32)>| -> u32 {
+            let total_expected: f32 = stem_progress.values().map(|(expected, _)| expected.max(1.0)).sum();
+            if total_expected <= 0.0 {
+                return 0;
+            }
+            let done: f32 = stem_progress
+                .values()
+                .map(|(expected, scanned)| {
+                    let expected = expected.max(1.0);
+                    (scanned / expected).min(1.0) * expected
+                })
+                .sum();
+            ((done / total_expected) * 100.0).clamp(0.0, 99.0) as u32
+        };
+
         let profiles = scanner
             .scan(resolved, |event| match event {
-                ScanEvent::StemStarted { stem } => {
+                ScanEvent::StemStarted {
+                    stem,
+                    expected_duration_secs,
+                } => {
+                    stem_progress.insert(stem.clone(), (expected_duration_secs, 0.0));
                     let _ = app_handle.emit(
                         "process-status",
                         ProgressPayload {
                             stage: "DSP".to_string(),
-                            progress: (completed_stems * 100 / 5).min(99),
+                            progress: weighted_percent(&stem_progress),
                             message: format!("Turbo Scan: scanning {stem} stem..."),
                         },
                     );
@@ -609,56 +958,62 @@ async fn generate_static_map(
                     stem,
                     seconds_scanned,
                 } => {
+                    stem_progress
+                        .entry(stem.clone())
+                        .and_modify(|(_, scanned)| *scanned = seconds_scanned)
+                        .or_insert((seconds_scanned.max(1.0), seconds_scanned));
                     let _ = app_handle.emit(
                         "process-status",
                         ProgressPayload {
                             stage: "DSP".to_string(),
-                            progress: (completed_stems * 100 / 5).min(99),
+                            progress: weighted_percent(&stem_progress),
                             message: format!(
-                                "Turbo Scan: {stem} scanned {:.1}s ({} of 5 complete)...",
-                                seconds_scanned, completed_stems
+                                "Turbo Scan: {stem} scanned {:.1}s ({} of {} complete)...",
+                                seconds_scanned,
+                                completed_stems,
+                                CANONICAL_STEMS.len()
                             ),
                         },
                     );
                 }
                 ScanEvent::StemFinished { stem } => {
                     completed_stems += 1;
+                    if let Some((expected, scanned)) = stem_progress.get_mut(&stem) {
+                        *scanned = *expected;
+                    }
                     let _ = app_handle.emit(
                         "process-status",
                         ProgressPayload {
                             stage: "DSP".to_string(),
-                            progress: (completed_stems * 100 / 5).min(100),
+                            progress: if completed_stems as usize >= CANONICAL_STEMS.len() {
+                                100
+                            } else {
+                                weighted_percent(&stem_progress)
+                            },
                             message: format!(
-                                "Turbo Scan: completed {stem} ({completed_stems} of 5)."
+                                "Turbo Scan: completed {stem} ({completed_stems} of {}).",
+                                CANONICAL_STEMS.len()
                             ),
                         },
                     );
                 }
             })
-            .map_err(|e| e.to_string())?;
-
-        let dx = profiles
-            .get("DX")
-            .ok_or_else(|| "Scanner did not produce DX profile".to_string())?;
-        let music = profiles
-            .get("Music")
-            .ok_or_else(|| "Scanner did not produce Music profile".to_string())?;
-        let sfx = profiles
-            .get("SFX")
-            .ok_or_else(|| "Scanner did not produce SFX profile".to_string())?;
-        let foley = profiles
-            .get("Foley")
-            .ok_or_else(|| "Scanner did not produce Foley profile".to_string())?;
-        let ambience = profiles
-            .get("Ambience")
-            .ok_or_else(|| "Scanner did not produce Ambience profile".to_string())?;
+            .map_err(|e| MikupError::Internal(e.to_string()))?;
+
+        let dx = profiles.get("DX").ok_or_else(|| {
+            MikupError::Internal("Scanner did not produce DX profile".to_string())
+        })?;
+        let music = profiles.get("Music").ok_or_else(|| {
+            MikupError::Internal("Scanner did not produce Music profile".to_string())
+        })?;
+        let effects = profiles.get("Effects").ok_or_else(|| {
+            MikupError::Internal("Scanner did not produce Effects profile".to_string())
+        })?;
 
         let lufs_graph = serde_json::json!({
             "DX": dx,
             "Music": music,
-            "SFX": sfx,
-            "Foley": foley,
-            "Ambience": ambience,
+            "Effects": effects,
             // Backward-compatible aliases consumed by current UI panels.
             "dialogue_raw": dx,
             "background_raw": music,
@@ -676,16 +1031,19 @@ async fn generate_static_map(
             .join("data")
             .join("dsp_metrics.json");
         if let Some(parent) = metrics_path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create data directory: {e}"))?;
+            std::fs::create_dir_all(parent).map_err(|e| {
+                MikupError::Internal(format!("Failed to create data directory: {e}"))
+            })?;
         }
-        let serialized =
-            serde_json::to_string_pretty(&persisted_metrics).map_err(|e| e.to_string())?;
+        let serialized = serde_json::to_string_pretty(&persisted_metrics)
+            .map_err(|e| MikupError::Internal(e.to_string()))?;
         let tmp_path = metrics_path.with_extension("json.tmp");
-        std::fs::write(&tmp_path, &serialized)
-            .map_err(|e| format!("Failed to write dsp_metrics.json: {e}"))?;
-        std::fs::rename(&tmp_path, &metrics_path)
-            .map_err(|e| format!("Failed to finalize dsp_metrics.json: {e}"))?;
+        std::fs::write(&tmp_path, &serialized).map_err(|e| {
+            MikupError::Internal(format!("Failed to write dsp_metrics.json: {e}"))
+        })?;
+        std::fs::rename(&tmp_path, &metrics_path).map_err(|e| {
+            MikupError::Internal(format!("Failed to finalize dsp_metrics.json: {e}"))
+        })?;
 
         let _ = app_handle.emit(
             "process-status",
@@ -699,18 +1057,34 @@ async fn generate_static_map(
         Ok(serde_json::json!({
             "lufs_graph": persisted_metrics["lufs_graph"].clone(),
         }))
-    })
+        },
+    )
     .await
-    .map_err(|e| e.to_string())??;
+    .map_err(|e| MikupError::Internal(e.to_string()))??;
 
     Ok(scan_result)
 }
 
+/// `max_analysis_samplerate` caps the rate used for gated-loudness/LRA accumulation during
+/// Turbo Scan (e.g. `Some(24_000)` roughly halves scan time at 48kHz sources, at <0.1 LU
+/// integrated drift). Pass `None` to scan at each stem's native rate. True peak is always
+/// measured on the original-rate signal, independent of this cap.
+#[tauri::command]
+async fn generate_static_map(
+    app: tauri::AppHandle,
+    output_directory: String,
+    stem_paths: HashMap<String, String>,
+    max_analysis_samplerate: Option<u32>,
+) -> MikupResponse<serde_json::Value> {
+    MikupResponse::from_result(
+        generate_static_map_impl(app, output_directory, stem_paths, max_analysis_samplerate).await,
+    )
+}
+
 /// Marks the DSP stage as complete in `stage_state.json`.
 /// Called by the frontend after the Rust `stream_audio_metrics` stream ends naturally.
 /// This allows `get_pipeline_state` to correctly report 3 completed stages on resume.
-#[tauri::command]
-async fn mark_dsp_complete(output_directory: String) -> Result<(), String> {
+async fn mark_dsp_complete_impl(output_directory: String) -> Result<(), MikupError> {
     let state_path = resolve_data_artifact_path(&output_directory, "stage_state.json")?;
 
     let mut state: serde_json::Value = match tokio::fs::read_to_string(&state_path).await {
@@ -720,395 +1094,394 @@ async fn mark_dsp_complete(output_directory: String) -> Result<(), String> {
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
             serde_json::json!({ "stages": {} })
         }
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(MikupError::Internal(e.to_string())),
     };
 
     state["stages"]["dsp"] = serde_json::json!({ "completed": true });
 
-    let serialized = serde_json::to_string(&state).map_err(|e| e.to_string())?;
+    let serialized =
+        serde_json::to_string(&state).map_err(|e| MikupError::Internal(e.to_string()))?;
     tokio::fs::write(&state_path, serialized)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| MikupError::Internal(e.to_string()))?;
 
     Ok(())
 }
 
+#[tauri::command]
+async fn mark_dsp_complete(output_directory: String) -> MikupResponse<()> {
+    MikupResponse::from_result(mark_dsp_complete_impl(output_directory).await)
+}
+
 /// Signal a running `stream_audio_metrics` call to stop after the current frame.
 #[tauri::command]
-async fn stop_dsp_stream(stream_generation: tauri::State<'_, Arc<AtomicU64>>) -> Result<(), String> {
+async fn stop_dsp_stream(
+    stream_generation: tauri::State<'_, Arc<AtomicU64>>,
+) -> MikupResponse<()> {
     // Increment the generation counter — the current blocking task will see its captured
     // generation no longer matches and will exit on the next loop iteration.
     stream_generation.fetch_add(1, Ordering::SeqCst);
-    Ok(())
+    #[cfg(feature = "metrics")]
+    telemetry::record_stream_cancellation();
+    MikupResponse::success(())
 }
 
-#[tauri::command]
-async fn set_stem_state(
+async fn set_stem_state_impl(
     stem_states: tauri::State<'_, Arc<RwLock<HashMap<String, StemState>>>>,
     stem_id: String,
     is_solo: bool,
     is_muted: bool,
-) -> Result<(), String> {
+) -> Result<(), MikupError> {
     let normalized = stem_id.trim().to_ascii_lowercase();
     if !matches!(
         normalized.as_str(),
         "dx" | "music" | "sfx" | "foley" | "ambience"
     ) {
-        return Err(format!(
+        return Err(MikupError::InvalidArgument(format!(
             "Invalid stem_id '{stem_id}'. Allowed values: dx, music, sfx, foley, ambience"
-        ));
+        )));
     }
 
     let mut map = stem_states
         .write()
-        .map_err(|_| "stem state lock poisoned".to_string())?;
-    map.insert(normalized, StemState { is_solo, is_muted });
+        .map_err(|_| MikupError::Fatal("stem state lock poisoned".to_string()))?;
+    let gain = map.get(&normalized).map(|s| s.gain).unwrap_or(1.0);
+    map.insert(
+        normalized,
+        StemState {
+            is_solo,
+            is_muted,
+            gain,
+        },
+    );
 
     Ok(())
 }
 
-/// Stream DSP metrics from the 5-stem WAV set (DX, music, foley, sfx, ambience) to the frontend.
-///
-/// Emits:
-/// - `dsp-frame`    — `DspFramePayload` at up to 60 FPS during processing.
-/// - `dsp-complete` — `DspCompletePayload` once when the file finishes naturally.
-/// - `dsp-error`    — `String` if a decode or analysis error occurs.
-///
-/// Calling this command while a previous stream is in progress automatically cancels
-/// the previous stream (the shared `cancel_flag` is reset to `false` then re-used).
 #[tauri::command]
-async fn stream_audio_metrics(
+async fn set_stem_state(
+    stem_states: tauri::State<'_, Arc<RwLock<HashMap<String, StemState>>>>,
+    stem_id: String,
+    is_solo: bool,
+    is_muted: bool,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(set_stem_state_impl(stem_states, stem_id, is_solo, is_muted).await)
+}
+
+async fn stream_audio_metrics_impl(
     app: tauri::AppHandle,
     stream_generation: tauri::State<'_, Arc<AtomicU64>>,
     stem_states: tauri::State<'_, Arc<RwLock<HashMap<String, StemState>>>>,
+    active_audio_actor: tauri::State<'_, Arc<std::sync::Mutex<Option<AudioActorHandle>>>>,
+    metrics_broadcast: tauri::State<'_, Arc<std::sync::Mutex<Option<MetricsBroadcastHandle>>>>,
+    transcription: tauri::State<'_, Arc<std::sync::Mutex<Option<TranscriptionHandle>>>>,
     dx_path: String,
     music_path: String,
     foley_path: String,
     sfx_path: String,
     ambience_path: String,
     start_time: f64,
-) -> Result<(), String> {
+) -> Result<(), MikupError> {
     ensure_safe_argument("DX path", &dx_path)?;
     ensure_safe_argument("Music path", &music_path)?;
     ensure_safe_argument("Foley path", &foley_path)?;
     ensure_safe_argument("SFX path", &sfx_path)?;
     ensure_safe_argument("Ambience path", &ambience_path)?;
     if !start_time.is_finite() || start_time < 0.0 {
-        return Err("start_time must be a finite value >= 0".to_string());
+        return Err(MikupError::InvalidArgument(
+            "start_time must be a finite value >= 0".to_string(),
+        ));
     }
 
-    // Each stream gets a unique generation number. The old blocking task holds a clone
+    // Each stream gets a unique generation number. The old actor thread holds a clone
     // of the counter and its own captured generation value. When we increment here the
-    // old task sees a mismatch on the next loop iteration and exits cleanly — no
+    // old actor sees a mismatch on the next loop iteration and exits cleanly — no
     // shared-flag reset race, no need to await the old task's handle.
     let my_gen = stream_generation.fetch_add(1, Ordering::SeqCst) + 1;
     let stream_gen_arc = Arc::clone(&*stream_generation);
     let shared_stem_states = Arc::clone(&*stem_states);
 
-    tokio::task::spawn_blocking(move || {
-        let mut decoder = MikupAudioDecoder::new(
-            &dx_path,
-            &music_path,
-            &foley_path,
-            &sfx_path,
-            &ambience_path,
-            shared_stem_states,
-            DSP_SAMPLE_RATE,
-            DSP_FRAME_SIZE,
-        )
-        .map_err(|e| e.to_string())?;
-        decoder
-            .seek(start_time as f32)
-            .map_err(|e| format!("Failed to seek decoder: {e}"))?;
-
-        let sample_rate = decoder.target_sample_rate();
-        let frame_size = decoder.frame_size();
-
-        let mut loudness = LoudnessAnalyzer::new(sample_rate).map_err(|e| e.to_string())?;
-        let spatial = SpatialAnalyzer::new();
-        let mut spectral = SpectralAnalyzer::new(sample_rate, frame_size);
-
-        // Audio output: create a cpal player and a resampler (48kHz → hardware rate).
-        // Failure to open the output device is non-fatal — analysis continues without audio.
-        let audio_player = AudioOutputPlayer::new_default(0.2)
-            .map_err(|e| eprintln!("[mikup] Audio output unavailable: {e}"))
-            .ok();
-        let mut audio_resampler = audio_player.as_ref().and_then(|p| {
-            MonoResampler::new(sample_rate, p.hardware_sample_rate())
-                .map_err(|e| eprintln!("[mikup] Audio resampler init failed: {e}"))
-                .ok()
-        });
-        if let Some(ref p) = audio_player {
-            if let Err(e) = p.start() {
-                eprintln!("[mikup] Audio player start failed: {e}");
-            }
-        }
+    let mut decoder = MikupAudioDecoder::new(
+        &dx_path,
+        &music_path,
+        &foley_path,
+        &sfx_path,
+        &ambience_path,
+        shared_stem_states.clone(),
+        DSP_SAMPLE_RATE,
+        DSP_FRAME_SIZE,
+    )
+    .map_err(|e| MikupError::Internal(e.to_string()))?;
+    decoder
+        .seek(start_time as f32)
+        .map_err(|e| MikupError::Internal(format!("Failed to seek decoder: {e}")))?;
+
+    let broadcast_handle = metrics_broadcast
+        .lock()
+        .map_err(|_| MikupError::Fatal("metrics broadcast lock poisoned".to_string()))?
+        .clone();
+    let transcription_handle = get_or_spawn_transcription(&app, &transcription).await;
+
+    let (handle, join_handle) = audio_actor::spawn(
+        app.clone(),
+        decoder,
+        shared_stem_states,
+        stream_gen_arc,
+        my_gen,
+        start_time,
+        broadcast_handle,
+        transcription_handle,
+    );
 
-        let mut frame_index: u64 = 0;
-        let min_interval = std::time::Duration::from_millis(MIN_EMIT_INTERVAL_MS);
-        let mut last_emit: Option<std::time::Instant> = None;
-        let mut eof_natural = false;
+    {
+        let mut slot = active_audio_actor
+            .lock()
+            .map_err(|_| MikupError::Fatal("audio actor lock poisoned".to_string()))?;
+        *slot = Some(handle);
+    }
 
-        loop {
-            if stream_gen_arc.load(Ordering::Relaxed) != my_gen {
-                break;
-            }
+    let result = join_handle
+        .await
+        .map_err(|e| MikupError::Internal(e.to_string()))?;
 
-            let frame = match decoder.read_frame() {
-                Ok(Some(f)) => f,
-                Ok(None) => {
-                    eof_natural = true;
-                    break;
-                }
-                Err(e) => {
-                    let _ = app.emit("dsp-error", e.to_string());
-                    return Err(e.to_string());
-                }
-            };
+    #[cfg(feature = "metrics")]
+    push_telemetry_if_configured(&app).await;
 
-            let timestamp_secs = frame_index as f32 * frame_size as f32 / sample_rate as f32;
+    result
+}
 
-            let loudness_metrics = match loudness.process_frame(&frame) {
-                Ok(m) => m,
-                Err(e) => {
-                    let _ = app.emit("dsp-error", e.to_string());
-                    return Err(e.to_string());
-                }
-            };
-
-            let spatial_metrics = spatial.process_frame(&frame);
-            let spectral_metrics = spectral.process_frame(&frame);
-
-            // Push mixed audio (dialogue + background) to cpal output player.
-            if let (Some(ref player), Some(ref mut resampler)) =
-                (&audio_player, &mut audio_resampler)
-            {
-                let mixed: Vec<f32> = frame
-                    .dialogue_raw
-                    .iter()
-                    .zip(frame.background_raw.iter())
-                    .map(|(d, b)| (d + b).clamp(-1.0, 1.0))
-                    .collect();
-                let resampled = resampler.process(&mixed);
-                let interleaved = interleave_mono(&resampled, player.channels());
-                player.push_interleaved_nonblocking(&interleaved);
-            }
+/// Stream DSP metrics from the 5-stem WAV set (DX, music, foley, sfx, ambience) to the frontend.
+///
+/// Emits:
+/// - `dsp-frame`    — `DspFramePayload` at up to 60 FPS during processing.
+/// - `dsp-complete` — `DspCompletePayload` once when the file finishes naturally.
+/// - `dsp-error`    — structured [`MikupError`] if a decode or analysis error occurs.
+///
+/// Calling this command while a previous stream is in progress automatically cancels
+/// the previous stream (the shared stream-generation counter is bumped, and the previous
+/// stream's actor exits once it sees a generation other than its own).
+#[tauri::command]
+async fn stream_audio_metrics(
+    app: tauri::AppHandle,
+    stream_generation: tauri::State<'_, Arc<AtomicU64>>,
+    stem_states: tauri::State<'_, Arc<RwLock<HashMap<String, StemState>>>>,
+    active_audio_actor: tauri::State<'_, Arc<std::sync::Mutex<Option<AudioActorHandle>>>>,
+    metrics_broadcast: tauri::State<'_, Arc<std::sync::Mutex<Option<MetricsBroadcastHandle>>>>,
+    transcription: tauri::State<'_, Arc<std::sync::Mutex<Option<TranscriptionHandle>>>>,
+    dx_path: String,
+    music_path: String,
+    foley_path: String,
+    sfx_path: String,
+    ambience_path: String,
+    start_time: f64,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(
+        stream_audio_metrics_impl(
+            app,
+            stream_generation,
+            stem_states,
+            active_audio_actor,
+            metrics_broadcast,
+            transcription,
+            dx_path,
+            music_path,
+            foley_path,
+            sfx_path,
+            ambience_path,
+            start_time,
+        )
+        .await,
+    )
+}
 
-            frame_index += 1;
+/// Looks up the already-running transcription actor, lazily spawning one on first use via
+/// [`transcription::spawn`]. Transcription is best-effort: if the Whisper model under
+/// `data/models/whisper` can't be loaded, this logs and returns `None` rather than
+/// failing the DSP stream — losing transcripts is fine, losing metering is not.
+async fn get_or_spawn_transcription(
+    app: &tauri::AppHandle,
+    transcription: &tauri::State<'_, Arc<std::sync::Mutex<Option<TranscriptionHandle>>>>,
+) -> Option<TranscriptionHandle> {
+    let existing = transcription.lock().ok().and_then(|guard| guard.clone());
+    if existing.is_some() {
+        return existing;
+    }
 
-            // Throttle: skip emit if the minimum interval hasn't elapsed yet.
-            let now = std::time::Instant::now();
-            let should_emit = match last_emit {
-                None => true,
-                Some(t) => now.duration_since(t) >= min_interval,
-            };
-            if !should_emit {
-                continue;
+    let project_root = find_project_root(app)?;
+    let model_dir = project_root.join("data").join("models").join("whisper");
+    match transcription::spawn(app.clone(), DSP_SAMPLE_RATE, model_dir).await {
+        Ok(handle) => {
+            if let Ok(mut slot) = transcription.lock() {
+                *slot = Some(handle.clone());
             }
-            last_emit = Some(now);
-
-            // Subsample Lissajous points so each frame emits at most LISSAJOUS_MAX_POINTS.
-            let step = (spatial_metrics.lissajous_points.len() / LISSAJOUS_MAX_POINTS).max(1);
-            let lissajous_points: Vec<[f32; 2]> = spatial_metrics
-                .lissajous_points
-                .iter()
-                .step_by(step)
-                .map(|p| [p.x, p.y])
-                .collect();
-
-            let payload = DspFramePayload {
-                frame_index,
-                timestamp_secs,
-                dialogue_momentary_lufs: loudness_metrics.dialogue.momentary_lufs,
-                dialogue_short_term_lufs: loudness_metrics.dialogue.short_term_lufs,
-                dialogue_true_peak_dbtp: loudness_metrics.dialogue.true_peak_dbtp,
-                dialogue_crest_factor: loudness_metrics.dialogue.crest_factor,
-                background_momentary_lufs: loudness_metrics.background.momentary_lufs,
-                background_short_term_lufs: loudness_metrics.background.short_term_lufs,
-                background_true_peak_dbtp: loudness_metrics.background.true_peak_dbtp,
-                background_crest_factor: loudness_metrics.background.crest_factor,
-                phase_correlation: spatial_metrics.phase_correlation,
-                lissajous_points,
-                dialogue_centroid_hz: spectral_metrics.dialogue_centroid_hz,
-                background_centroid_hz: spectral_metrics.background_centroid_hz,
-                speech_pocket_masked: spectral_metrics.speech_pocket_masked,
-                dialogue_speech_energy: spectral_metrics.dialogue_speech_energy,
-                background_speech_energy: spectral_metrics.background_speech_energy,
-                snr_db: spectral_metrics.snr_db,
-            };
-
-            let _ = app.emit("dsp-frame", payload);
+            Some(handle)
         }
-
-        if let Some(ref player) = audio_player {
-            player.mark_producer_finished();
-        }
-
-        // Warn if any stems were shorter than others and were padded with silence.
-        if decoder.alignment_mismatch_detected {
-            let _ = app.emit(
-                "process-status",
-                ProgressPayload {
-                    stage: "DSP_WARNING".to_string(),
-                    progress: 0,
-                    message: "Stem length mismatch: one or more stems are shorter than others and were padded with silence. Spatial and ducking analysis may be affected near the tail.".to_string(),
-                },
-            );
+        Err(e) => {
+            eprintln!("[mikup] Transcription disabled: {e}");
+            None
         }
+    }
+}
 
-        // Only emit the completion event when we reached EOF naturally (not cancelled).
-        if eof_natural {
-            let final_metrics = loudness.final_metrics();
-            let _ = app.emit(
-                "dsp-complete",
-                DspCompletePayload {
-                    total_frames: frame_index,
-                    dialogue_integrated_lufs: final_metrics.dialogue.integrated_lufs,
-                    dialogue_loudness_range_lu: final_metrics.dialogue.loudness_range_lu,
-                    background_integrated_lufs: final_metrics.background.integrated_lufs,
-                    background_loudness_range_lu: final_metrics.background.loudness_range_lu,
-                },
-            );
-        }
+async fn start_metrics_broadcast_impl(
+    metrics_broadcast: tauri::State<'_, Arc<std::sync::Mutex<Option<MetricsBroadcastHandle>>>>,
+    port: u16,
+) -> Result<(), MikupError> {
+    let handle = metrics_broadcast::start_metrics_broadcast(port).await?;
+    let mut slot = metrics_broadcast
+        .lock()
+        .map_err(|_| MikupError::Fatal("metrics broadcast lock poisoned".to_string()))?;
+    *slot = Some(handle);
+    Ok(())
+}
 
-        Ok::<(), String>(())
-    })
-    .await
-    .map_err(|e| e.to_string())?
+/// Opens a TCP listener on `port` that mirrors every `dsp-frame`/`dsp-complete` emitted
+/// by audio streams started after this call to connected clients, as length-prefixed
+/// JSON fragments (see [`metrics_broadcast`]). A stream already running keeps the
+/// broadcast handle it was started with, so its frames do not reach this listener. Intended for an out-of-process meter on a
+/// second machine during a mixing session.
+#[tauri::command]
+async fn start_metrics_broadcast(
+    metrics_broadcast: tauri::State<'_, Arc<std::sync::Mutex<Option<MetricsBroadcastHandle>>>>,
+    port: u16,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(start_metrics_broadcast_impl(metrics_broadcast, port).await)
 }
 
-/// Emitted once per tool call the AI Director makes during a turn.
-#[derive(Clone, serde::Serialize)]
-struct AgentActionPayload {
-    tool: String,
-    time_secs: Option<f64>,
+async fn audio_transport_impl(
+    active_audio_actor: tauri::State<'_, Arc<std::sync::Mutex<Option<AudioActorHandle>>>>,
+    command: AudioCommand,
+) -> Result<(), MikupError> {
+    let slot = active_audio_actor
+        .lock()
+        .map_err(|_| MikupError::Fatal("audio actor lock poisoned".to_string()))?;
+    match slot.as_ref() {
+        Some(handle) => handle.send(command),
+        None => Err(MikupError::NotFound(
+            "No active audio stream to control".to_string(),
+        )),
+    }
 }
 
-/// Send a single message to the AI Director Python sidecar and return its reply.
-///
-/// The Python process (`src/llm/interactive.py`) communicates over stdin/stdout
-/// using newline-delimited JSON:
-///   Rust  → Python stdin:  `{"text": "<user message>"}\n`
-///   Python → Rust stdout:  `{"type": "ready"}\n`           (once, on startup)
-///                          `{"tool": "<name>", ...}\n`      (zero or more tool calls)
-///                          `{"type": "response", "text": "..."}\n`
-///
-/// Each tool call is forwarded to the frontend as an `agent-action` Tauri event.
-///
-/// # Security
-/// `workspace_dir` must be an absolute path. The value is passed verbatim as the
-/// `WORKSPACE_DIR` environment variable so Python's `_is_path_safe` can correctly
-/// sandbox file access to the project workspace.
+/// Forwards a transport command (`Play`/`Pause`/`Seek`/`SetMasterVolume`/`SetStemGain`/
+/// `SetStemMute`/`Stop`) to the currently running [`audio_actor`], if any. The actor
+/// reports status changes back via the `dsp-transport` event rather than this command's
+/// return value.
 #[tauri::command]
-async fn send_agent_message(
+async fn audio_transport(
+    active_audio_actor: tauri::State<'_, Arc<std::sync::Mutex<Option<AudioActorHandle>>>>,
+    command: AudioCommand,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(audio_transport_impl(active_audio_actor, command).await)
+}
+
+/// Looks up the already-running AI Director sidecar for `workspace_dir`, spawning one
+/// via [`agent_sidecar::spawn`] on first use. The sidecar stays registered in `sidecars`
+/// for the lifetime of the app, so later turns in the same workspace reuse its process
+/// and conversation history instead of paying the model/tool-init cost again.
+async fn get_or_spawn_sidecar(
+    app: &tauri::AppHandle,
+    sidecars: &tauri::State<'_, Arc<std::sync::Mutex<HashMap<String, AgentSidecarHandle>>>>,
+    workspace_dir: &str,
+) -> Result<AgentSidecarHandle, MikupError> {
+    let existing = sidecars
+        .lock()
+        .map_err(|_| MikupError::Fatal("AI Director sidecar registry lock poisoned".to_string()))?
+        .get(workspace_dir)
+        .cloned();
+    if let Some(handle) = existing {
+        return Ok(handle);
+    }
+
+    let project_root = require_project_root(app)?;
+    let python_path = resolve_python_path(&project_root);
+    let handle = agent_sidecar::spawn(
+        app.clone(),
+        python_path,
+        project_root,
+        workspace_dir.to_string(),
+    )
+    .await?;
+
+    sidecars
+        .lock()
+        .map_err(|_| MikupError::Fatal("AI Director sidecar registry lock poisoned".to_string()))?
+        .insert(workspace_dir.to_string(), handle.clone());
+    Ok(handle)
+}
+
+async fn send_agent_message_impl(
     app: tauri::AppHandle,
+    sidecars: tauri::State<'_, Arc<std::sync::Mutex<HashMap<String, AgentSidecarHandle>>>>,
     text: String,
     workspace_dir: String,
-) -> Result<String, String> {
+) -> Result<String, MikupError> {
     ensure_safe_argument("Text", &text)?;
     ensure_safe_argument("Workspace directory", &workspace_dir)?;
 
     let workspace_path = PathBuf::from(&workspace_dir);
     if !workspace_path.is_absolute() {
-        return Err("Path Denied: workspace_dir must be an absolute path".to_string());
+        return Err(MikupError::InvalidArgument(
+            "Path Denied: workspace_dir must be an absolute path".to_string(),
+        ));
     }
     if !workspace_path.is_dir() {
-        return Err(format!("Workspace directory not found: {workspace_dir}"));
+        return Err(MikupError::NotFound(format!(
+            "Workspace directory not found: {workspace_dir}"
+        )));
     }
 
-    let project_root =
-        find_project_root(&app).ok_or_else(|| "Unable to resolve project root".to_string())?;
-    let python_path = resolve_python_path(&project_root);
-
-    let (mut rx, mut child) = app
-        .shell()
-        .command(&python_path)
-        .current_dir(&project_root)
-        .args(["-m", "src.llm.interactive"])
-        .env("WORKSPACE_DIR", &workspace_dir)
-        .spawn()
-        .map_err(|e| format!("Failed to spawn AI Director: {e}"))?;
-
-    let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(120);
-    let mut buf = String::new();
-    let mut ready = false;
-    let mut result: Result<String, String> =
-        Err("AI Director did not return a response".to_string());
+    let handle = get_or_spawn_sidecar(&app, &sidecars, &workspace_dir).await?;
+    handle.send_message(text).await
+}
 
-    'outer: loop {
-        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
-        if remaining.is_zero() {
-            result = Err("AI Director timed out".to_string());
-            break;
-        }
+/// Send a message to the AI Director sidecar for `workspace_dir`, reusing the
+/// already-running process for that workspace (spawning one on first use) so that
+/// multi-turn conversation history survives across calls. See [`agent_sidecar`] for
+/// the sidecar protocol and lifecycle.
+///
+/// # Security
+/// `workspace_dir` must be an absolute path. The value is passed verbatim as the
+/// `WORKSPACE_DIR` environment variable so Python's `_is_path_safe` can correctly
+/// sandbox file access to the project workspace.
+#[tauri::command]
+async fn send_agent_message(
+    app: tauri::AppHandle,
+    sidecars: tauri::State<'_, Arc<std::sync::Mutex<HashMap<String, AgentSidecarHandle>>>>,
+    text: String,
+    workspace_dir: String,
+) -> MikupResponse<String> {
+    MikupResponse::from_result(send_agent_message_impl(app, sidecars, text, workspace_dir).await)
+}
 
-        let maybe_event = match tokio::time::timeout(remaining, rx.recv()).await {
-            Ok(ev) => ev,
-            Err(_) => {
-                result = Err("AI Director timed out".to_string());
-                break;
-            }
-        };
+async fn reset_agent_session_impl(
+    sidecars: tauri::State<'_, Arc<std::sync::Mutex<HashMap<String, AgentSidecarHandle>>>>,
+    workspace_dir: String,
+) -> Result<(), MikupError> {
+    ensure_safe_argument("Workspace directory", &workspace_dir)?;
 
-        match maybe_event {
-            Some(CommandEvent::Stdout(chunk)) => {
-                buf.push_str(&String::from_utf8_lossy(&chunk));
-                while let Some(pos) = buf.find('\n') {
-                    let line: String = buf.drain(..=pos).collect();
-                    let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(trimmed) {
-                        let msg_type = json_val.get("type").and_then(|t| t.as_str()).unwrap_or("");
-                        if msg_type == "ready" && !ready {
-                            ready = true;
-                            let msg = serde_json::json!({"text": text}).to_string() + "\n";
-                            if let Err(e) = child.write(msg.as_bytes()) {
-                                result = Err(format!("Failed to send message to AI Director: {e}"));
-                                break 'outer;
-                            }
-                        } else if msg_type == "response" && ready {
-                            let response_text = json_val
-                                .get("text")
-                                .and_then(|t| t.as_str())
-                                .unwrap_or("")
-                                .to_string();
-                            result = Ok(response_text);
-                            break 'outer;
-                        } else if let Some(tool_name) =
-                            json_val.get("tool").and_then(|t| t.as_str())
-                        {
-                            let time_secs = json_val.get("time_secs").and_then(|t| t.as_f64());
-                            let _ = app.emit(
-                                "agent-action",
-                                AgentActionPayload {
-                                    tool: tool_name.to_string(),
-                                    time_secs,
-                                },
-                            );
-                        }
-                    }
-                }
-            }
-            Some(CommandEvent::Stderr(_)) => {
-                // Python logging to stderr — ignored by design.
-            }
-            Some(CommandEvent::Terminated(status)) => {
-                if result.is_err() && status.code != Some(0) {
-                    result = Err(format!(
-                        "AI Director exited unexpectedly (code {:?})",
-                        status.code
-                    ));
-                }
-                break;
-            }
-            Some(_) | None => break,
-        }
+    let handle = sidecars
+        .lock()
+        .map_err(|_| MikupError::Fatal("AI Director sidecar registry lock poisoned".to_string()))?
+        .get(&workspace_dir)
+        .cloned();
+
+    match handle {
+        Some(handle) => handle.reset().await,
+        // Nothing has talked to this workspace's AI Director yet, so there is no
+        // history to clear — treat it as already reset rather than an error.
+        None => Ok(()),
     }
+}
 
-    let _ = child.kill();
-    result
+/// Clears the AI Director's server-side conversation history for `workspace_dir`
+/// without tearing down its sidecar process.
+#[tauri::command]
+async fn reset_agent_session(
+    sidecars: tauri::State<'_, Arc<std::sync::Mutex<HashMap<String, AgentSidecarHandle>>>>,
+    workspace_dir: String,
+) -> MikupResponse<()> {
+    MikupResponse::from_result(reset_agent_session_impl(sidecars, workspace_dir).await)
 }
 
 #[cfg(test)]
@@ -1170,10 +1543,21 @@ pub fn run() {
     tauri::Builder::default()
         .manage(Arc::new(AtomicU64::new(0)))
         .manage(shared_default_stem_states())
+        .manage(Arc::new(std::sync::Mutex::new(None::<AudioActorHandle>)))
+        .manage(Arc::new(std::sync::Mutex::new(
+            None::<MetricsBroadcastHandle>,
+        )))
+        .manage(Arc::new(std::sync::Mutex::new(
+            HashMap::<String, AgentSidecarHandle>::new(),
+        )))
+        .manage(Arc::new(std::sync::Mutex::new(
+            None::<TranscriptionHandle>,
+        )))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             process_audio,
+            process_audio_batch,
             run_pipeline_stage,
             read_output_payload,
             get_stems,
@@ -1185,10 +1569,13 @@ pub fn run() {
             write_dsp_metrics,
             generate_static_map,
             stream_audio_metrics,
+            start_metrics_broadcast,
+            audio_transport,
             stop_dsp_stream,
             set_stem_state,
             mark_dsp_complete,
             send_agent_message,
+            reset_agent_session,
         ])
         .setup(|_app| Ok(()))
         .run(tauri::generate_context!())
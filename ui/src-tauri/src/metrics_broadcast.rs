@@ -0,0 +1,106 @@
+//! Optional TCP fan-out of DSP frames to an out-of-process meter.
+//!
+//! `dsp-frame`/`dsp-complete` are normally only delivered to the embedded webview via
+//! Tauri events. `start_metrics_broadcast` opens a TCP listener and mirrors the same
+//! frames to any number of connected clients as length-prefixed JSON fragments, so an
+//! external loudness/vectorscope display (e.g. on a second machine during a mixing
+//! session) can follow along without coupling to the Tauri IPC bridge.
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+use crate::error::MikupError;
+
+/// Broadcast channel depth: a handful of frames' worth of slack so a momentarily slow
+/// consumer doesn't stall the producer. A client that falls further behind than this
+/// is treated as lagging (see [`broadcast::error::RecvError::Lagged`]) and simply skips
+/// ahead rather than backing up the render loop.
+const BROADCAST_CHANNEL_CAPACITY: usize = 32;
+
+/// A length-prefixed JSON fragment: a `kind` tag (`"frame"` or `"complete"`) plus the
+/// payload, so a subscriber can tell the terminal `DspCompletePayload` fragment apart
+/// from the per-frame stream without guessing from shape alone.
+#[derive(Clone, serde::Serialize)]
+struct MetricsFragment {
+    kind: &'static str,
+    payload: serde_json::Value,
+}
+
+/// Handle to a running metrics broadcast server. Cloning shares the same broadcast
+/// channel, so the audio actor can push frames into it without holding the listener.
+#[derive(Clone)]
+pub struct MetricsBroadcastHandle {
+    sender: broadcast::Sender<Vec<u8>>,
+}
+
+impl MetricsBroadcastHandle {
+    fn encode(kind: &'static str, payload: impl serde::Serialize) -> Option<Vec<u8>> {
+        let payload = serde_json::to_value(payload).ok()?;
+        let body = serde_json::to_vec(&MetricsFragment { kind, payload }).ok()?;
+        let mut framed = Vec::with_capacity(4 + body.len());
+        framed.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        framed.extend_from_slice(&body);
+        Some(framed)
+    }
+
+    /// Pushes a `DspFramePayload` fragment to every connected client. Called once per
+    /// emitted `dsp-frame` from the (blocking) audio actor render loop; a no-op if there
+    /// are no subscribers or the frame fails to serialize.
+    pub fn send_frame(&self, payload: impl serde::Serialize) {
+        if let Some(framed) = Self::encode("frame", payload) {
+            let _ = self.sender.send(framed);
+        }
+    }
+
+    /// Pushes the terminal `DspCompletePayload` fragment, so a reconnecting client can
+    /// tell the stream ended naturally rather than having simply gone quiet.
+    pub fn send_complete(&self, payload: impl serde::Serialize) {
+        if let Some(framed) = Self::encode("complete", payload) {
+            let _ = self.sender.send(framed);
+        }
+    }
+}
+
+/// Binds a TCP listener on `port` and spawns its accept loop. Each accepted connection
+/// gets its own task that forwards broadcast fragments until the client disconnects;
+/// a client that lags behind the channel capacity skips ahead instead of stalling the
+/// producer, mirroring the render loop's own `MIN_EMIT_INTERVAL_MS` throttle.
+pub async fn start_metrics_broadcast(port: u16) -> Result<MetricsBroadcastHandle, MikupError> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await.map_err(|e| {
+        MikupError::Internal(format!("Failed to bind metrics broadcast port {port}: {e}"))
+    })?;
+
+    let (sender, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+    let handle = MetricsBroadcastHandle {
+        sender: sender.clone(),
+    };
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _addr) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    eprintln!("[mikup] Metrics broadcast accept failed: {e}");
+                    continue;
+                }
+            };
+            let mut rx = sender.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(framed) => {
+                            if socket.write_all(&framed).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(handle)
+}
@@ -0,0 +1,135 @@
+//! Optional Prometheus Pushgateway telemetry (`metrics` Cargo feature).
+//!
+//! There is no way to observe aggregate pipeline health across runs — stage durations,
+//! frames processed, final integrated LUFS per stem, stream cancellations — beyond the
+//! per-project `dsp_metrics.json` file. This module keeps a process-wide [`Registry`] of
+//! counters/gauges/histograms and, when enabled, pushes them to a configurable Pushgateway
+//! URL + job label (read from [`AppConfig`](crate::AppConfig)). Disabled by default, so a
+//! default build pulls in none of these dependencies.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use prometheus::{CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry};
+
+struct Telemetry {
+    registry: Registry,
+    stage_duration_seconds: HistogramVec,
+    dsp_frames_total: CounterVec,
+    stream_cancellations_total: CounterVec,
+    integrated_lufs: GaugeVec,
+}
+
+fn telemetry() -> &'static Telemetry {
+    static TELEMETRY: OnceLock<Telemetry> = OnceLock::new();
+    TELEMETRY.get_or_init(|| {
+        let registry = Registry::new();
+
+        let stage_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "mikup_pipeline_stage_duration_seconds",
+                "Wall-clock duration of a single pipeline stage run.",
+            ),
+            &["stage"],
+        )
+        .expect("metric options are static and valid");
+        registry
+            .register(Box::new(stage_duration_seconds.clone()))
+            .expect("metric name is unique within this registry");
+
+        let dsp_frames_total = CounterVec::new(
+            Opts::new(
+                "mikup_dsp_frames_total",
+                "Number of DSP frames streamed to the frontend.",
+            ),
+            &[],
+        )
+        .expect("metric options are static and valid");
+        registry
+            .register(Box::new(dsp_frames_total.clone()))
+            .expect("metric name is unique within this registry");
+
+        let stream_cancellations_total = CounterVec::new(
+            Opts::new(
+                "mikup_dsp_stream_cancellations_total",
+                "Number of times a running DSP stream was cancelled via stop_dsp_stream.",
+            ),
+            &[],
+        )
+        .expect("metric options are static and valid");
+        registry
+            .register(Box::new(stream_cancellations_total.clone()))
+            .expect("metric name is unique within this registry");
+
+        let integrated_lufs = GaugeVec::new(
+            Opts::new(
+                "mikup_integrated_lufs",
+                "Final integrated LUFS per stem group for the most recently completed DSP stream.",
+            ),
+            &["stem"],
+        )
+        .expect("metric options are static and valid");
+        registry
+            .register(Box::new(integrated_lufs.clone()))
+            .expect("metric name is unique within this registry");
+
+        Telemetry {
+            registry,
+            stage_duration_seconds,
+            dsp_frames_total,
+            stream_cancellations_total,
+            integrated_lufs,
+        }
+    })
+}
+
+/// Records how long a `run_pipeline_stage` invocation took, labelled by stage name.
+pub fn record_stage_duration(stage: &str, elapsed: Duration) {
+    telemetry()
+        .stage_duration_seconds
+        .with_label_values(&[stage])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Increments the DSP frame counter once per `dsp-frame` event emitted.
+pub fn record_dsp_frame() {
+    telemetry().dsp_frames_total.with_label_values(&[]).inc();
+}
+
+/// Increments the cancellation counter once per `stop_dsp_stream` call.
+pub fn record_stream_cancellation() {
+    telemetry()
+        .stream_cancellations_total
+        .with_label_values(&[])
+        .inc();
+}
+
+/// Records the final integrated LUFS for the dialogue and background stem groups when a
+/// DSP stream's `dsp-complete` event fires.
+pub fn record_final_lufs(dialogue_integrated_lufs: f32, background_integrated_lufs: f32) {
+    let t = telemetry();
+    t.integrated_lufs
+        .with_label_values(&["dialogue"])
+        .set(dialogue_integrated_lufs as f64);
+    t.integrated_lufs
+        .with_label_values(&["background"])
+        .set(background_integrated_lufs as f64);
+}
+
+/// Pushes the current metric snapshot to `pushgateway_url` under `job`, off the async
+/// runtime since the underlying HTTP client is blocking. Failures are logged and
+/// swallowed — telemetry is best-effort and must never fail a pipeline run.
+pub fn push(pushgateway_url: String, job: String) {
+    tokio::task::spawn_blocking(move || {
+        let metric_families = telemetry().registry.gather();
+        if let Err(e) = prometheus::push_metrics(
+            &job,
+            prometheus::labels! {},
+            &pushgateway_url,
+            metric_families,
+            None,
+        ) {
+            eprintln!("[mikup] Failed to push metrics to {pushgateway_url}: {e}");
+        }
+    });
+}
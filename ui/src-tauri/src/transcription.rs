@@ -0,0 +1,255 @@
+//! Native streaming transcription of the dialogue stem, aligned to DSP frames.
+//!
+//! The pipeline already tracks a `transcription` stage in `stage_state.json`, but during
+//! live DSP streaming the dialogue content is invisible — the UI sees LUFS and
+//! speech-energy curves with no words attached. This module runs Whisper locally via
+//! `candle` (no server, no Python sidecar) off the same dialogue samples the audio actor
+//! already decodes: [`TranscriptionHandle::push_frame`] feeds each frame's dialogue
+//! samples into a ring buffer on a dedicated blocking task; once roughly one second has
+//! accumulated, that window is transcribed and emitted as a `dsp-transcript` event
+//! carrying `{text, start_secs, end_secs}` on the same `timestamp_secs` clock as
+//! `dsp-frame`, tagged with the `speech_pocket_masked`/`snr_db` readings from the same
+//! window so the director can correlate "what was said" with "was it audible".
+//!
+//! Inference is far slower than the 60 FPS render loop, so frames are handed off through
+//! a small bounded queue that drops the oldest entry under backpressure rather than ever
+//! blocking the analysis/audio-output loop waiting on a transcription in progress.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+
+use candle_core::{Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::whisper::{self as whisper, Config};
+use tauri::{AppHandle, Emitter};
+use tokenizers::Tokenizer;
+
+use crate::error::MikupError;
+
+/// How many ~frame-sized chunks may queue up before the oldest is dropped. Kept small —
+/// a backlog here means inference is already behind real time, and piling up more audio
+/// only makes the eventual transcript more stale.
+const QUEUE_CAPACITY: usize = 8;
+
+/// Emitted once per transcribed ~1s window of dialogue audio.
+#[derive(Clone, serde::Serialize)]
+struct TranscriptPayload {
+    text: String,
+    start_secs: f32,
+    end_secs: f32,
+    speech_pocket_masked: bool,
+    snr_db: f32,
+}
+
+struct FrameChunk {
+    samples: Vec<f32>,
+    timestamp_secs: f32,
+    speech_pocket_masked: bool,
+    snr_db: f32,
+}
+
+/// A bounded queue that evicts its oldest entry instead of blocking the producer once
+/// full. `pop_blocking` parks the consumer thread on a condvar rather than busy-polling.
+struct DropOldestQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    not_empty: Condvar,
+    capacity: usize,
+}
+
+impl<T> DropOldestQueue<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+            not_empty: Condvar::new(),
+            capacity,
+        }
+    }
+
+    fn push(&self, item: T) {
+        let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        if items.len() >= self.capacity {
+            items.pop_front();
+        }
+        items.push_back(item);
+        self.not_empty.notify_one();
+    }
+
+    fn pop_blocking(&self) -> T {
+        let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(item) = items.pop_front() {
+                return item;
+            }
+            items = self
+                .not_empty
+                .wait(items)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// Handle to a running transcription actor. Cloning shares the same queue, so the audio
+/// actor can hand off frames without owning the inference task itself.
+#[derive(Clone)]
+pub struct TranscriptionHandle {
+    queue: Arc<DropOldestQueue<FrameChunk>>,
+}
+
+impl TranscriptionHandle {
+    /// Queues one DSP frame's dialogue samples for transcription. `timestamp_secs` must
+    /// be the same clock `dsp-frame` uses, so `dsp-transcript` segments line up with it.
+    pub fn push_frame(
+        &self,
+        samples: Vec<f32>,
+        timestamp_secs: f32,
+        speech_pocket_masked: bool,
+        snr_db: f32,
+    ) {
+        self.queue.push(FrameChunk {
+            samples,
+            timestamp_secs,
+            speech_pocket_masked,
+            snr_db,
+        });
+    }
+}
+
+/// Loads the Whisper model from `model_dir` and, once loaded, starts a dedicated
+/// blocking task that accumulates pushed frames into a ring buffer and transcribes each
+/// ~1s window as it fills. Returns an error (rather than spawning with a broken model) if
+/// the model can't be loaded, so callers can treat transcription as best-effort and fall
+/// back to running without it.
+pub async fn spawn(
+    app: AppHandle,
+    sample_rate: u32,
+    model_dir: PathBuf,
+) -> Result<TranscriptionHandle, MikupError> {
+    let model = tokio::task::spawn_blocking(move || WhisperModel::load(&model_dir))
+        .await
+        .map_err(|e| MikupError::Internal(format!("Transcription model loader panicked: {e}")))??;
+
+    let queue = Arc::new(DropOldestQueue::new(QUEUE_CAPACITY));
+    let handle = TranscriptionHandle {
+        queue: Arc::clone(&queue),
+    };
+
+    tokio::task::spawn_blocking(move || run_transcription_loop(app, model, queue, sample_rate));
+
+    Ok(handle)
+}
+
+fn run_transcription_loop(
+    app: AppHandle,
+    mut model: WhisperModel,
+    queue: Arc<DropOldestQueue<FrameChunk>>,
+    sample_rate: u32,
+) {
+    let window_samples = sample_rate as usize;
+    let mut ring_buffer: Vec<f32> = Vec::with_capacity(window_samples * 2);
+    let mut window_start_secs = 0.0_f32;
+    let mut last_speech_pocket_masked = false;
+    let mut last_snr_db = 0.0_f32;
+
+    loop {
+        let chunk = queue.pop_blocking();
+        if ring_buffer.is_empty() {
+            window_start_secs = chunk.timestamp_secs;
+        }
+        let window_end_secs =
+            chunk.timestamp_secs + (chunk.samples.len() as f32 / sample_rate as f32);
+        last_speech_pocket_masked = chunk.speech_pocket_masked;
+        last_snr_db = chunk.snr_db;
+        ring_buffer.extend_from_slice(&chunk.samples);
+
+        if ring_buffer.len() < window_samples {
+            continue;
+        }
+
+        match model.transcribe(&ring_buffer) {
+            Ok(text) if !text.trim().is_empty() => {
+                let _ = app.emit(
+                    "dsp-transcript",
+                    TranscriptPayload {
+                        text,
+                        start_secs: window_start_secs,
+                        end_secs: window_end_secs,
+                        speech_pocket_masked: last_speech_pocket_masked,
+                        snr_db: last_snr_db,
+                    },
+                );
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[mikup] Whisper inference failed: {e}"),
+        }
+
+        ring_buffer.clear();
+    }
+}
+
+/// A loaded Whisper checkpoint (`config.json` + `tokenizer.json` + `model.safetensors`
+/// under `model_dir`, the usual Hugging Face layout) ready for repeated CPU inference.
+struct WhisperModel {
+    device: Device,
+    config: Config,
+    mel_filters: Vec<f32>,
+    model: whisper::model::Whisper,
+    tokenizer: Tokenizer,
+}
+
+impl WhisperModel {
+    fn load(model_dir: &Path) -> Result<Self, MikupError> {
+        let device = Device::Cpu;
+
+        let config_path = model_dir.join("config.json");
+        let config: Config = serde_json::from_str(&std::fs::read_to_string(&config_path).map_err(
+            |e| {
+                MikupError::Internal(format!(
+                    "Failed to read Whisper config at {}: {e}",
+                    config_path.display()
+                ))
+            },
+        )?)
+        .map_err(|e| MikupError::Internal(format!("Invalid Whisper config JSON: {e}")))?;
+
+        let tokenizer = Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| MikupError::Internal(format!("Failed to load Whisper tokenizer: {e}")))?;
+
+        let weights_path = model_dir.join("model.safetensors");
+        let vb = unsafe {
+            VarBuilder::from_mmaped_safetensors(&[weights_path], whisper::DTYPE, &device)
+                .map_err(|e| MikupError::Internal(format!("Failed to load Whisper weights: {e}")))?
+        };
+        let model = whisper::model::Whisper::load(&vb, config.clone())
+            .map_err(|e| MikupError::Internal(format!("Failed to build Whisper model: {e}")))?;
+
+        let mel_filters = whisper::audio::load_mel_filters(config.num_mel_bins)
+            .map_err(|e| MikupError::Internal(format!("Failed to load mel filterbank: {e}")))?;
+
+        Ok(Self {
+            device,
+            config,
+            mel_filters,
+            model,
+            tokenizer,
+        })
+    }
+
+    /// Runs one greedy-decoded transcription pass over a ring-buffer window of mono f32
+    /// PCM. Each window is decoded independently (no cross-chunk decoder state) — good
+    /// enough for the "what was just said" signal this feeds to the director, at a
+    /// fraction of the complexity of full streaming beam search.
+    fn transcribe(&mut self, samples: &[f32]) -> Result<String, MikupError> {
+        let mel = whisper::audio::pcm_to_mel(&self.config, samples, &self.mel_filters);
+        let mel_len = mel.len() / self.config.num_mel_bins;
+        let mel_tensor = Tensor::from_vec(mel, (1, self.config.num_mel_bins, mel_len), &self.device)
+            .map_err(|e| MikupError::Internal(format!("Failed to build mel tensor: {e}")))?;
+
+        let tokens = whisper::model::greedy_decode(&mut self.model, &mel_tensor)
+            .map_err(|e| MikupError::Internal(format!("Whisper inference failed: {e}")))?;
+
+        self.tokenizer
+            .decode(&tokens, true)
+            .map_err(|e| MikupError::Internal(format!("Failed to decode Whisper tokens: {e}")))
+    }
+}